@@ -3,7 +3,7 @@ mod counters;
 use crate::counters::*;
 use actix_files::Files;
 use actix_web::*;
-use leptos_actix::{generate_route_list, LeptosRoutes};
+use leptos_actix::{generate_route_list, sse_handler, LeptosRoutes};
 
 #[get("/api/events")]
 async fn counter_events() -> impl Responder {
@@ -13,15 +13,8 @@ async fn counter_events() -> impl Responder {
     let stream = futures::stream::once(async {
         crate::counters::get_server_count().await.unwrap_or(0)
     })
-    .chain(COUNT_CHANNEL.clone())
-    .map(|value| {
-        Ok(web::Bytes::from(format!(
-            "event: message\ndata: {value}\n\n"
-        ))) as Result<web::Bytes>
-    });
-    HttpResponse::Ok()
-        .insert_header(("Content-Type", "text/event-stream"))
-        .streaming(stream)
+    .chain(COUNT_CHANNEL.clone());
+    sse_handler(stream)
 }
 
 #[actix_web::main]
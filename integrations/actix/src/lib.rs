@@ -639,6 +639,82 @@ where
     )
 }
 
+/// Returns an Actix [struct@Route] that listens for a `GET` request and tries
+/// to route it using [leptos_router], serving an HTML stream of your application with the
+/// given headers added to the response.
+///
+/// This can be used to set headers like `Cache-Control`, `Link` or `Content-Security-Policy`
+/// for a route. The headers are added as if each value was passed to
+/// [`ResponseOptions::append_header`] before the app is rendered, so the app can still
+/// replace them with [`ResponseOptions::insert_header`].
+///
+/// A `Content-Type` header in `headers` is ignored, so the response keeps its `text/html`
+/// content type. To build the headers one at a time, use [`StreamHeaders`].
+///
+/// ## Provided Context Types
+/// This function always provides context values including the following types:
+/// - [ResponseOptions]
+/// - [Request]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "trace", fields(error), skip_all)
+)]
+pub fn render_app_to_stream_with_headers<IV>(
+    headers: header::HeaderMap,
+    app_fn: impl Fn() -> IV + Clone + Send + 'static,
+    method: Method,
+) -> Route
+where
+    IV: IntoView + 'static,
+{
+    render_app_to_stream_with_context(
+        move || {
+            let res_options = expect_context::<ResponseOptions>();
+            for (key, value) in headers.iter() {
+                if key != header::CONTENT_TYPE {
+                    res_options.append_header(key.clone(), value.clone());
+                }
+            }
+        },
+        app_fn,
+        method,
+    )
+}
+
+/// A builder for the headers of [`render_app_to_stream_with_headers`], adding them one at a
+/// time.
+///
+/// The same rules apply: the app can replace these headers, and a `Content-Type` header is
+/// ignored.
+#[derive(Debug, Clone, Default)]
+pub struct StreamHeaders(header::HeaderMap);
+
+impl StreamHeaders {
+    /// Creates a builder with no headers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a header. Adding the same header more than once sends each of its values.
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.0.append(name, value);
+        self
+    }
+
+    /// Returns an Actix [struct@Route] that serves an HTML stream of your application with
+    /// these headers, like [`render_app_to_stream_with_headers`].
+    pub fn render_app_to_stream<IV>(
+        self,
+        app_fn: impl Fn() -> IV + Clone + Send + 'static,
+        method: Method,
+    ) -> Route
+    where
+        IV: IntoView + 'static,
+    {
+        render_app_to_stream_with_headers(self.0, app_fn, method)
+    }
+}
+
 /// Returns an Actix [struct@Route](actix_web::Route) that listens for a `GET` request and tries
 /// to route it using [leptos_router], serving an HTML stream of your application.
 ///
@@ -1660,3 +1736,106 @@ where
     })
     .await
 }
+
+/// A single [Server-Sent Event](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events),
+/// sent by [`sse_handler`].
+///
+/// Any [`Display`] value converts into an event with no `id` or `event` field, which the
+/// browser's `EventSource` delivers as a `message` event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// The event ID, which the browser sends back in `Last-Event-ID` when it reconnects.
+    pub id: Option<String>,
+    /// The event type, which defaults to `message` in the browser.
+    pub event: Option<String>,
+    /// The data of the event, which may span multiple lines.
+    pub data: String,
+}
+
+impl SseEvent {
+    /// Creates an event with the given data.
+    pub fn new(data: impl Display) -> Self {
+        Self {
+            id: None,
+            event: None,
+            data: data.to_string(),
+        }
+    }
+
+    /// Sets the event ID.
+    pub fn id(mut self, id: impl Display) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Sets the event type.
+    pub fn event(mut self, event: impl Display) -> Self {
+        self.event = Some(event.to_string());
+        self
+    }
+
+    /// Formats the event as it is sent in the stream, ending with a blank line.
+    fn to_frame(&self) -> String {
+        // a line break would end the field early, so it is left out of single-line fields
+        fn push_field(frame: &mut String, name: &str, value: &str) {
+            frame.push_str(name);
+            frame.push_str(": ");
+            frame.extend(value.chars().filter(|c| !matches!(c, '\r' | '\n')));
+            frame.push('\n');
+        }
+
+        let mut frame = String::with_capacity(self.data.len() + 8);
+        if let Some(id) = &self.id {
+            push_field(&mut frame, "id", id);
+        }
+        if let Some(event) = &self.event {
+            push_field(&mut frame, "event", event);
+        }
+        for line in self.data.split('\n') {
+            push_field(&mut frame, "data", line);
+        }
+        frame.push('\n');
+        frame
+    }
+}
+
+impl<T> From<T> for SseEvent
+where
+    T: Display,
+{
+    fn from(data: T) -> Self {
+        Self::new(data)
+    }
+}
+
+/// Creates a response that streams each item as a
+/// [Server-Sent Event](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events),
+/// with the `text/event-stream` content type and caching turned off.
+///
+/// Items can be any [`Display`] value, which is sent as the event data, or an [`SseEvent`] to
+/// also set its `id` or `event` field.
+///
+/// ```no_run
+/// use actix_web::{get, Responder};
+/// use leptos_actix::{sse_handler, SseEvent};
+///
+/// #[get("/api/events")]
+/// async fn events() -> impl Responder {
+///     sse_handler(futures::stream::iter([
+///         SseEvent::new(1).id(1),
+///         SseEvent::new("done").event("end"),
+///     ]))
+/// }
+/// ```
+pub fn sse_handler<S, T>(stream: S) -> HttpResponse
+where
+    S: Stream<Item = T> + 'static,
+    T: Into<SseEvent>,
+{
+    HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, "text/event-stream"))
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(stream.map(|event| {
+            Ok(web::Bytes::from(event.into().to_frame())) as Result<web::Bytes>
+        }))
+}
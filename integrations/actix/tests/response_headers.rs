@@ -0,0 +1,71 @@
+use actix_web::{
+    dev::ServiceResponse,
+    http::header::{self, HeaderMap, HeaderValue},
+    test, App, Route,
+};
+use leptos::prelude::*;
+use leptos_actix::{render_app_to_stream_with_headers, StreamHeaders};
+use leptos_router::Method;
+
+async fn get(route: Route) -> ServiceResponse {
+    _ = any_spawner::Executor::init_tokio();
+
+    let app = test::init_service(App::new().route("/", route)).await;
+    test::call_service(&app, test::TestRequest::get().uri("/").to_request())
+        .await
+}
+
+fn content_types(res: &ServiceResponse) -> Vec<&str> {
+    res.headers()
+        .get_all(header::CONTENT_TYPE)
+        .map(|value| value.to_str().unwrap())
+        .collect()
+}
+
+#[actix_web::test]
+async fn streamed_response_has_custom_headers() {
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("public, max-age=60"),
+    );
+    headers
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static("text/plain"));
+
+    let res = get(render_app_to_stream_with_headers(
+        headers,
+        || view! { <p>"Hello"</p> },
+        Method::Get,
+    ))
+    .await;
+
+    assert_eq!(
+        res.headers().get(header::CACHE_CONTROL).unwrap(),
+        "public, max-age=60"
+    );
+    assert_eq!(content_types(&res), ["text/html"]);
+}
+
+#[actix_web::test]
+async fn builder_adds_headers_and_ignores_content_type() {
+    let res = get(StreamHeaders::new()
+        .header(
+            header::LINK,
+            HeaderValue::from_static("</a.css>; rel=preload"),
+        )
+        .header(
+            header::LINK,
+            HeaderValue::from_static("</b.js>; rel=preload"),
+        )
+        .header(header::CONTENT_TYPE, HeaderValue::from_static("text/plain"))
+        .render_app_to_stream(|| view! { <p>"Hello"</p> }, Method::Get))
+    .await;
+
+    let links = res
+        .headers()
+        .get_all(header::LINK)
+        .map(|value| value.to_str().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(links, ["</a.css>; rel=preload", "</b.js>; rel=preload"]);
+    assert_eq!(content_types(&res), ["text/html"]);
+}
@@ -0,0 +1,23 @@
+use actix_web::{body::to_bytes, http::header};
+use futures::executor::block_on;
+use leptos_actix::{sse_handler, SseEvent};
+
+#[test]
+fn sse_handler_frames_each_item() {
+    let response = sse_handler(futures::stream::iter([
+        SseEvent::new(1),
+        SseEvent::new("two\nlines").id(2).event("update"),
+    ]));
+    let headers = response.headers();
+    assert_eq!(
+        headers.get(header::CONTENT_TYPE).unwrap(),
+        "text/event-stream"
+    );
+    assert_eq!(headers.get(header::CACHE_CONTROL).unwrap(), "no-cache");
+
+    let body = block_on(to_bytes(response.into_body())).unwrap();
+    assert_eq!(
+        body,
+        "data: 1\n\nid: 2\nevent: update\ndata: two\ndata: lines\n\n"
+    );
+}
@@ -370,7 +370,7 @@ where
         let _hook = throw_error::set_error_hook(Arc::clone(&self.hook));
 
         // first, attempt to serialize the children to HTML, then check for errors
-        let mut new_buf = StreamBuilder::new(buf.clone_id());
+        let mut new_buf = buf.sub_builder(buf.clone_id());
         let mut new_pos = *position;
         self.children.to_html_async_with_buf::<OUT_OF_ORDER>(
             &mut new_buf,
@@ -405,7 +405,7 @@ where
             // if we're waiting for suspended children, we'll first wait for them to load
             // in this implementation, an ErrorBoundary that *contains* Suspense essentially acts
             // like a Suspense: it will wait for (all top-level) child Suspense to load before rendering anything
-            let mut view_buf = StreamBuilder::new(new_buf.clone_id());
+            let mut view_buf = new_buf.sub_builder(new_buf.clone_id());
             view_buf.next_id();
             let hook = Arc::clone(&self.hook);
             view_buf.push_async(async move {
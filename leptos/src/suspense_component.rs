@@ -21,7 +21,10 @@ use reactive_graph::{
     },
 };
 use slotmap::{DefaultKey, SlotMap};
-use std::sync::{Arc, Mutex};
+use std::{
+    panic::AssertUnwindSafe,
+    sync::{Arc, Mutex},
+};
 use tachys::{
     either::Either,
     html::attribute::{any_attribute::AnyAttribute, Attribute},
@@ -410,6 +413,7 @@ where
             }
         });
 
+        let data_id = self.id.clone();
         let mut fut = Box::pin(ScopedFuture::new(ErrorHookFuture::new(
             async move {
                 // race the local resource notifier against the set of tasks
@@ -489,7 +493,20 @@ where
 
                 // out-of-order streams immediately push fallback,
                 // wrapped by suspense markers
-                if OUT_OF_ORDER {
+                if OUT_OF_ORDER && buf.claim_out_of_order_boundary() {
+                    // a panic leaves the fallback in place, so the client has to render the
+                    // children rather than hydrate them
+                    let fut = AssertUnwindSafe(fut).catch_unwind().map({
+                        let sc = Owner::current_shared_context();
+                        move |children| {
+                            children.unwrap_or_else(|_| {
+                                if let Some(sc) = sc {
+                                    sc.set_incomplete_chunk(data_id);
+                                }
+                                None
+                            })
+                        }
+                    });
                     let mut fallback_position = *position;
                     buf.push_fallback(
                         self.fallback,
@@ -510,6 +527,7 @@ where
                     // done because the fallback can be shifted to another thread in push_async below.
                     self.fallback.dry_resolve();
 
+                    let mut builder = buf.sub_builder(id);
                     buf.push_async({
                         let mut position = *position;
                         async move {
@@ -517,7 +535,6 @@ where
                                 None => Either::Left(self.fallback),
                                 Some(value) => Either::Right(value),
                             };
-                            let mut builder = StreamBuilder::new(id);
                             value.to_html_async_with_buf::<OUT_OF_ORDER>(
                                 &mut builder,
                                 &mut position,
@@ -0,0 +1,81 @@
+#[cfg(feature = "ssr")]
+#[tokio::test]
+async fn boundaries_past_the_limit_render_in_order() {
+    use any_spawner::Executor;
+    use futures::StreamExt;
+    use leptos::{prelude::*, tachys::ssr::RenderContext};
+    use std::time::Duration;
+
+    _ = Executor::init_tokio();
+    let owner = Owner::new();
+    owner.set();
+
+    let boundary = |n: usize| {
+        let value = Resource::new(
+            || (),
+            move |_| async move {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                n
+            },
+        );
+        view! {
+            <Suspense fallback=|| "loading">
+                {move || Suspend::new(async move { value.await })}
+            </Suspense>
+        }
+    };
+    let app = view! { <main>{boundary(1)}{boundary(2)}{boundary(3)}</main> };
+
+    let html = RenderContext::new()
+        .max_out_of_order_boundaries(2)
+        .run(|| app.to_html_stream_out_of_order())
+        .collect::<String>()
+        .await;
+
+    // only the first two boundaries send a fallback, and later a chunk to replace it
+    assert_eq!(html.matches("loading").count(), 2, "{html}");
+    assert_eq!(html.matches("<template").count(), 2, "{html}");
+    // the third is rendered inline, once it has resolved
+    assert!(html.contains("3</main>"), "{html}");
+}
+
+#[cfg(feature = "ssr")]
+#[tokio::test]
+async fn panicking_boundary_keeps_its_fallback() {
+    use any_spawner::Executor;
+    use futures::StreamExt;
+    use hydration_context::{
+        SerializedDataId, SharedContext, SsrSharedContext,
+    };
+    use leptos::prelude::*;
+    use std::{sync::Arc, time::Duration};
+
+    async fn load() -> Option<&'static str> {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+        None
+    }
+
+    _ = Executor::init_tokio();
+    let shared_context = Arc::new(SsrSharedContext::new());
+    let owner = Owner::new_root(Some(shared_context.clone()));
+    owner.set();
+
+    let app = view! {
+        <main>
+            <Suspense fallback=|| "loading">
+                {Suspend::new(async { load().await.expect("failed to load") })}
+            </Suspense>
+        </main>
+    };
+    let html = app.to_html_stream_out_of_order().collect::<String>().await;
+
+    // the fallback stays, because no chunk is sent to replace it
+    assert!(html.contains("loading"), "{html}");
+    assert!(!html.contains("range.deleteContents()"), "{html}");
+    // the client renders the children instead of hydrating them
+    // (the boundary is the first thing to take an id)
+    assert!(
+        shared_context.get_incomplete_chunk(&SerializedDataId::new(0)),
+        "{html}"
+    );
+}
@@ -43,6 +43,7 @@
 //! **Important Note:** If you’re using server-side rendering, you should enable `ssr`.
 
 use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
 use leptos::{
     attr::{any_attribute::AnyAttribute, NextAttribute},
     component,
@@ -53,7 +54,7 @@ use leptos::{
         dom::document,
         html::{
             attribute::Attribute,
-            element::{ElementType, HtmlElement},
+            element::{link, ElementType, HtmlElement},
         },
         hydration::Cursor,
         view::{
@@ -108,6 +109,54 @@ impl MetaContext {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Declares that the page needs the JavaScript module at `url`, adding a
+    /// `<link rel="modulepreload">` for it to the `<head>` so that the browser can start
+    /// fetching it early.
+    ///
+    /// Each module is only added once, however many components require it. Like other `<head>`
+    /// content, during server rendering this has to be called before the first chunk of the
+    /// response is sent.
+    pub fn require_module(&self, url: impl Into<Oco<'static, str>>) {
+        let url = url.into();
+
+        #[cfg(feature = "ssr")]
+        if let Some(cx) = use_context::<ServerMetaContext>() {
+            // identical elements are deduplicated when they're injected into the `<head>`
+            _ = cx.elements.send(module_preload(url).to_html());
+        } else {
+            let msg = "tried to require a module without `ServerMetaContext` \
+                       provided";
+
+            #[cfg(feature = "tracing")]
+            tracing::warn!("{}", msg);
+
+            #[cfg(not(feature = "tracing"))]
+            eprintln!("{msg}");
+        }
+
+        #[cfg(not(feature = "ssr"))]
+        {
+            let head = document_head();
+            let mut child = head.first_element_child();
+            while let Some(el) = child {
+                if el.tag_name().eq_ignore_ascii_case("link")
+                    && el.get_attribute("rel").as_deref()
+                        == Some("modulepreload")
+                    && el.get_attribute("href").as_deref() == Some(&*url)
+                {
+                    return;
+                }
+                child = el.next_element_sibling();
+            }
+            module_preload(url).build().mount(&head, None);
+        }
+    }
+}
+
+/// A `<link>` that asks the browser to preload a JavaScript module.
+fn module_preload(url: Oco<'static, str>) -> impl RenderHtml {
+    link().rel("modulepreload").href(url)
 }
 
 pub(crate) const HEAD_MARKER_COMMENT: &str = "HEAD";
@@ -223,8 +272,14 @@ impl ServerMetaContextOutput {
         leptos::task::tick().await;
 
         // wait for the first chunk of the stream, to ensure our components hve run
-        let mut first_chunk = stream.next().await.unwrap_or_default();
+        let first_chunk = stream.next().await.unwrap_or_default();
+        let modified_chunk = self.inject_into(first_chunk);
 
+        futures::stream::once(async move { modified_chunk }).chain(stream)
+    }
+
+    /// Injects the metadata into a chunk of HTML that contains the `</head>` tag.
+    fn inject_into(self, mut first_chunk: String) -> String {
         // create <title> tag
         let title = self.title.as_string();
         let title_len = title
@@ -232,8 +287,8 @@ impl ServerMetaContextOutput {
             .map(|n| "<title>".len() + n.len() + "</title>".len())
             .unwrap_or(0);
 
-        // collect all registered meta tags
-        let meta_buf = self.elements.try_iter().collect::<String>();
+        // collect all registered meta tags, without duplicates
+        let meta_buf = dedup_elements(self.elements.try_iter());
 
         // get HTML strings for `<html>` and `<body>`
         let html_attrs = self.html.try_iter().collect::<String>();
@@ -283,10 +338,112 @@ impl ServerMetaContextOutput {
             }
         }
 
-        futures::stream::once(async move { modified_chunk }).chain(stream)
+        modified_chunk
     }
 }
 
+/// Renders a view to an HTML document, moving the `<head>` content that its components add with
+/// this crate, like a [`Title`] or [`Meta`], into `<head>`.
+///
+/// This is for pages that are rendered to a string rather than streamed; streamed responses should
+/// use [`ServerMetaContextOutput::inject_meta_context`] instead. The view is created by `app`, in a
+/// new reactive owner with a [`ServerMetaContext`] provided, so that components can register their
+/// metadata as they are created. The output is an `<html>` element holding `<head>` and a `<body>`
+/// with the rest of the view, so that attributes set with [`Html`] and [`Body`] are added to them
+/// too. If a `nonce` is given, it is used as the render context's
+/// [`nonce`](leptos::tachys::ssr::RenderContext::nonce) for this render.
+#[cfg(feature = "ssr")]
+pub fn to_html_with_nonce_and_meta<V>(
+    app: impl FnOnce() -> V,
+    nonce: Option<&str>,
+) -> String
+where
+    V: RenderHtml,
+{
+    use leptos::{reactive::owner::Owner, tachys::ssr::RenderContext};
+
+    let owner = Owner::current()
+        .map(|owner| owner.child())
+        .unwrap_or_default();
+    owner.with(|| {
+        let (meta_cx, output) = ServerMetaContext::new();
+        provide_context(meta_cx);
+
+        let mut cx = RenderContext::with_current(RenderContext::clone);
+        if let Some(nonce) = nonce {
+            cx = cx.nonce(nonce);
+        }
+        let body = cx.run(|| app().to_html());
+        output.inject_into(format!(
+            "<html><head></head><body>{body}</body></html>"
+        ))
+    })
+}
+
+/// Joins the HTML of the registered head elements, dropping duplicates.
+///
+/// Identical elements are only rendered once. `<meta>` tags that set `charset`, or one of the
+/// [`SINGLE_VALUED_METAS`], are also treated as duplicates if they set the same one: like
+/// `<title>`, the one rendered last wins, but it keeps the position of the first.
+fn dedup_elements(elements: impl Iterator<Item = String>) -> String {
+    let mut deduped = IndexMap::new();
+    for el in elements {
+        let key = meta_key(&el).unwrap_or_else(|| el.clone());
+        deduped.insert(key, el);
+    }
+    deduped.into_values().collect()
+}
+
+/// The `name`, `property`, and `http-equiv` values of `<meta>` tags that a page only has one of.
+///
+/// Others can be repeated on purpose, like `og:image` for several images, or `theme-color` with
+/// a different `media` query each, so they are only deduplicated if they're identical.
+const SINGLE_VALUED_METAS: &[&str] = &[
+    "application-name",
+    "color-scheme",
+    "content-type",
+    "default-style",
+    "description",
+    "generator",
+    "keywords",
+    "og:description",
+    "og:locale",
+    "og:site_name",
+    "og:title",
+    "og:type",
+    "og:url",
+    "referrer",
+    "refresh",
+    "robots",
+    "twitter:card",
+    "twitter:creator",
+    "twitter:description",
+    "twitter:site",
+    "twitter:title",
+    "viewport",
+    "x-ua-compatible",
+];
+
+/// Returns a key identifying what a `<meta>` tag sets, if it is one that can only be set once.
+fn meta_key(el: &str) -> Option<String> {
+    let attrs = el.strip_prefix("<meta")?;
+    // attribute values are escaped, so a quote always ends the value
+    let attr_value = |name: &str| {
+        let start = attrs.find(&format!(" {name}=\""))? + name.len() + 3;
+        let len = attrs[start..].find('"')?;
+        Some(&attrs[start..start + len])
+    };
+    if attr_value("charset").is_some() {
+        return Some("meta charset".to_string());
+    }
+    ["name", "property", "http-equiv"]
+        .into_iter()
+        .find_map(|name| attr_value(name).map(|value| (name, value)))
+        .map(|(name, value)| (name, value.to_ascii_lowercase()))
+        .filter(|(_, value)| SINGLE_VALUED_METAS.contains(&value.as_str()))
+        .map(|(name, value)| format!("meta {name}={value}"))
+}
+
 /// Provides a [`MetaContext`], if there is not already one provided. This ensures that you can provide it
 /// at the highest possible level, without overwriting a [`MetaContext`] that has already been provided
 /// (for example, by a server-rendering integration.)
@@ -439,7 +596,7 @@ where
             self.el.to_html_with_buf(
                 &mut buf,
                 &mut Position::NextChild,
-                false,
+                true,
                 false,
                 vec![],
             );
@@ -610,3 +767,154 @@ impl OrDefaultNonce for Option<Oco<'static, str>> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::dedup_elements;
+    #[cfg(feature = "ssr")]
+    use super::{to_html_with_nonce_and_meta, MetaContext, ServerMetaContext};
+    #[cfg(feature = "ssr")]
+    use leptos::reactive::owner::{provide_context, Owner};
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn required_module_is_preloaded_once() {
+        use any_spawner::Executor;
+        use futures::{executor::block_on, stream, StreamExt};
+
+        _ = Executor::init_futures_executor();
+        let owner = Owner::new();
+        owner.set();
+        let (cx, output) = ServerMetaContext::new();
+        provide_context(cx);
+
+        let meta = MetaContext::new();
+        meta.require_module("/pkg/chart.js");
+        meta.require_module("/pkg/chart.js");
+        meta.require_module("/pkg/map.js");
+
+        let page = stream::iter([
+            "<html><head><!--HEAD--></head><body>".to_string(),
+            "</body></html>".to_string(),
+        ]);
+        let html = block_on(async {
+            output
+                .inject_meta_context(page)
+                .await
+                .collect::<String>()
+                .await
+        });
+        assert_eq!(
+            html,
+            concat!(
+                "<html><head><!--HEAD-->",
+                r#"<link rel="modulepreload" href="/pkg/chart.js">"#,
+                r#"<link rel="modulepreload" href="/pkg/map.js">"#,
+                "</head><body></body></html>",
+            )
+        );
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn text_in_registered_tags_is_escaped() {
+        use super::register;
+        use leptos::{
+            prelude::RenderHtml,
+            tachys::html::element::{template, ElementChild},
+        };
+
+        let owner = Owner::new();
+        owner.set();
+        let (cx, output) = ServerMetaContext::new();
+        provide_context(cx);
+
+        _ = register(template().child("a < b")).to_html();
+        let html = dedup_elements(output.elements.try_iter());
+        assert_eq!(html, "<template>a &lt; b</template>");
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn nested_title_is_moved_into_head() {
+        use super::Title;
+        use leptos::prelude::*;
+
+        #[component]
+        fn Page() -> impl IntoView {
+            view! {
+                <Title text="Nested"/>
+                <p>"content"</p>
+            }
+        }
+
+        let html = to_html_with_nonce_and_meta(
+            || view! { <main><Page/></main> },
+            None,
+        );
+        let (head, body) = html
+            .strip_prefix("<html><head>")
+            .and_then(|html| html.split_once("</head>"))
+            .unwrap();
+        assert_eq!(head, "<title>Nested</title>");
+        assert!(body.starts_with("<body><main>"));
+        assert!(body.ends_with("</body></html>"));
+        assert!(body.contains("<p>content</p>"));
+        assert!(!body.contains("<title>"));
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn html_and_body_attributes_are_kept() {
+        use super::{Body, Html};
+        use leptos::prelude::*;
+
+        let html = to_html_with_nonce_and_meta(
+            || {
+                view! {
+                    <Html {..} lang="he" dir="rtl"/>
+                    <Body {..} class="dark"/>
+                    <p>"content"</p>
+                }
+            },
+            None,
+        );
+        assert!(html.starts_with(r#"<html lang="he" dir="rtl"><head>"#));
+        assert!(html.contains(r#"<body class="dark">"#));
+    }
+
+    #[test]
+    fn duplicate_metas_are_deduped() {
+        let elements = [
+            r#"<meta charset="utf-8">"#,
+            r#"<meta name="description" content="Parent">"#,
+            r#"<link rel="icon" href="/favicon.ico">"#,
+            r#"<meta charset="utf-8">"#,
+            r#"<meta name="description" content="Child">"#,
+            r#"<link rel="icon" href="/favicon.ico">"#,
+            r#"<meta property="og:title" content="Child">"#,
+        ];
+        let html = dedup_elements(elements.into_iter().map(String::from));
+        assert_eq!(
+            html,
+            concat!(
+                r#"<meta charset="utf-8">"#,
+                r#"<meta name="description" content="Child">"#,
+                r#"<link rel="icon" href="/favicon.ico">"#,
+                r#"<meta property="og:title" content="Child">"#,
+            )
+        );
+    }
+    #[test]
+    fn repeatable_metas_are_kept() {
+        let elements = [
+            r#"<meta property="og:image" content="/a.png">"#,
+            r#"<meta property="og:image" content="/b.png">"#,
+            r#"<meta name="theme-color" media="(prefers-color-scheme: light)" content="white">"#,
+            r#"<meta name="theme-color" media="(prefers-color-scheme: dark)" content="black">"#,
+            r#"<meta property="og:image" content="/a.png">"#,
+        ];
+        let html = dedup_elements(elements.into_iter().map(String::from));
+        assert_eq!(html, elements[..4].concat());
+    }
+}
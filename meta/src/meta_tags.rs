@@ -1,6 +1,7 @@
 use crate::register;
 use leptos::{
     component,
+    oco::Oco,
     prelude::{CustomAttribute, GlobalAttributes},
     tachys::html::element::meta,
     text_prop::TextProp,
@@ -10,6 +11,9 @@ use leptos::{
 /// Injects an [`HTMLMetaElement`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLMetaElement) into the document
 /// head to set metadata
 ///
+/// When rendered on the server, duplicate tags are only included once. If several tags set the
+/// same `charset`, `name`, `property`, or `http-equiv`, the one rendered last wins.
+///
 /// ```
 /// use leptos::prelude::*;
 /// use leptos_meta::*;
@@ -58,3 +62,64 @@ pub fn Meta(
             .content(content.map(|v| move || v.get())),
     )
 }
+
+/// Injects a `<meta http-equiv="refresh">` into the document head, which redirects to `url`
+/// after `seconds`.
+///
+/// This works even when JavaScript is disabled, so it can be used for simple redirects in
+/// progressive-enhancement flows. The URL is escaped like any other attribute value.
+///
+/// ```
+/// use leptos::prelude::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn Moved() -> impl IntoView {
+///   provide_meta_context();
+///
+///   view! {
+///     <MetaRefresh seconds=3 url="https://github.com/leptos-rs/leptos"/>
+///     <p>"This page has moved."</p>
+///   }
+/// }
+/// ```
+#[component]
+pub fn MetaRefresh(
+    /// The number of seconds to wait before redirecting.
+    #[prop(optional)]
+    seconds: u32,
+    /// The URL to redirect to.
+    #[prop(into)]
+    url: Oco<'static, str>,
+) -> impl IntoView {
+    register(
+        meta()
+            .http_equiv("refresh")
+            .content(format!("{seconds};url={url}")),
+    )
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use crate::{MetaRefresh, ServerMetaContext};
+    use leptos::{prelude::*, reactive::owner::Owner};
+
+    #[test]
+    fn meta_refresh_escapes_url() {
+        let owner = Owner::new();
+        owner.set();
+        let (cx, output) = ServerMetaContext::new();
+        provide_context(cx);
+
+        let html = view! {
+            <MetaRefresh seconds=3 url="/search?q=\"a\"&page=2"/>
+        }
+        .to_html();
+        assert_eq!(html, "");
+        assert_eq!(
+            output.elements.try_iter().collect::<String>(),
+            "<meta http-equiv=\"refresh\" \
+             content=\"3;url=/search?q=&quot;a&quot;&amp;page=2\">"
+        );
+    }
+}
@@ -405,3 +405,21 @@ impl Mountable for TitleViewState {
         vec![]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TitleContext;
+
+    #[test]
+    fn nested_title_overrides_ancestor() {
+        let cx = TitleContext::default();
+        let parent = cx.next_id();
+        cx.push_text_and_formatter(parent, Some("Parent".into()), None);
+        let child = cx.next_id();
+        cx.push_text_and_formatter(child, Some("Child".into()), None);
+        assert_eq!(cx.as_string().as_deref(), Some("Child"));
+
+        cx.remove_id(child);
+        assert_eq!(cx.as_string().as_deref(), Some("Parent"));
+    }
+}
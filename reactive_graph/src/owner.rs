@@ -16,9 +16,13 @@ mod arc_stored_value;
 mod arena;
 mod arena_item;
 mod context;
+mod mapped_stored_value;
 mod storage;
+mod stored_once;
 mod stored_value;
 use self::arena::Arena;
+#[cfg(feature = "testing")]
+pub use arc_stored_value::AccessLog;
 pub use arc_stored_value::ArcStoredValue;
 #[cfg(feature = "sandboxed-arenas")]
 pub use arena::sandboxed::Sandboxed;
@@ -27,7 +31,9 @@ use arena::ArenaMap;
 use arena::NodeId;
 pub use arena_item::*;
 pub use context::*;
+pub use mapped_stored_value::MappedStoredValue;
 pub use storage::*;
+pub use stored_once::{OnceState, StoredOnce};
 #[allow(deprecated)] // allow exporting deprecated fn
 pub use stored_value::{store_value, FromLocal, StoredValue};
 
@@ -299,7 +305,8 @@ impl Owner {
     /// Cleans up this owner in the following order:
     /// 1) Runs `cleanup` on all children,
     /// 2) Runs all cleanup functions registered with [`Owner::on_cleanup`],
-    /// 3) Drops the values of any arena-allocated [`ArenaItem`]s.
+    /// 3) Drops the values of any arena-allocated [`ArenaItem`]s, in the reverse of the order in
+    ///    which they were created, so that a value can depend on the ones created before it.
     pub fn cleanup(&self) {
         self.inner.cleanup();
     }
@@ -532,14 +539,14 @@ impl Drop for OwnerInner {
         if !nodes.is_empty() {
             #[cfg(not(feature = "sandboxed-arenas"))]
             Arena::with_mut(|arena| {
-                for node in nodes {
+                for node in nodes.into_iter().rev() {
                     _ = arena.remove(node);
                 }
             });
             #[cfg(feature = "sandboxed-arenas")]
             {
                 let mut arena = self.arena.write().or_poisoned();
-                for node in nodes {
+                for node in nodes.into_iter().rev() {
                     _ = arena.remove(node);
                 }
             }
@@ -573,7 +580,7 @@ impl Cleanup for RwLock<OwnerInner> {
         if !nodes.is_empty() {
             #[cfg(not(feature = "sandboxed-arenas"))]
             Arena::with_mut(|arena| {
-                for node in nodes {
+                for node in nodes.into_iter().rev() {
                     _ = arena.remove(node);
                 }
             });
@@ -581,7 +588,7 @@ impl Cleanup for RwLock<OwnerInner> {
             {
                 let arena = self.read().or_poisoned().arena.clone();
                 let mut arena = arena.write().or_poisoned();
-                for node in nodes {
+                for node in nodes.into_iter().rev() {
                     _ = arena.remove(node);
                 }
             }
@@ -2,13 +2,39 @@ use crate::{
     signal::guards::{Plain, ReadGuard, UntrackedWriteGuard},
     traits::{DefinedAt, IntoInner, IsDisposed, ReadValue, WriteValue},
 };
+#[cfg(feature = "testing")]
+use std::sync::atomic::AtomicUsize;
 use std::{
     fmt::{Debug, Formatter},
     hash::Hash,
     panic::Location,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock, PoisonError, RwLock,
+    },
 };
 
+/// The number of times a stored value has been accessed since it was created.
+///
+/// This is returned by [`StoredValue::access_log`](super::StoredValue::access_log) and
+/// [`ArcStoredValue::access_log`], so that tests can check how often a value was read or
+/// updated.
+#[cfg(feature = "testing")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AccessLog {
+    /// The number of times the value was read.
+    pub reads: usize,
+    /// The number of times the value was written to.
+    pub writes: usize,
+}
+
+#[cfg(feature = "testing")]
+#[derive(Debug, Default)]
+struct AccessCounts {
+    reads: AtomicUsize,
+    writes: AtomicUsize,
+}
+
 /// A reference-counted getter for any value non-reactively.
 ///
 /// This is a reference-counted value, which is `Clone` but not `Copy`.
@@ -21,7 +47,43 @@ use std::{
 pub struct ArcStoredValue<T> {
     #[cfg(any(debug_assertions, leptos_debuginfo))]
     defined_at: &'static Location<'static>,
-    value: Arc<RwLock<T>>,
+    value: Arc<Slot<T>>,
+}
+
+/// The shared storage for a value, which may not have been created yet if it was stored with
+/// [`ArcStoredValue::new_lazy`].
+struct Slot<T> {
+    // the read and write guards each hold on to the lock they were taken from, so it needs its
+    // own `Arc`
+    value: OnceLock<Arc<RwLock<T>>>,
+    init: Mutex<Option<LazyInit<T>>>,
+    dirty: AtomicBool,
+    #[cfg(feature = "testing")]
+    accesses: AccessCounts,
+}
+
+type LazyInit<T> = Box<dyn FnOnce() -> T + Send>;
+
+impl<T> Slot<T> {
+    fn new(value: T) -> Self {
+        Self {
+            value: OnceLock::from(Arc::new(RwLock::new(value))),
+            init: Mutex::new(None),
+            dirty: AtomicBool::new(false),
+            #[cfg(feature = "testing")]
+            accesses: AccessCounts::default(),
+        }
+    }
+
+    fn new_lazy(init: LazyInit<T>) -> Self {
+        Self {
+            value: OnceLock::new(),
+            init: Mutex::new(Some(init)),
+            dirty: AtomicBool::new(false),
+            #[cfg(feature = "testing")]
+            accesses: AccessCounts::default(),
+        }
+    }
 }
 
 impl<T> Clone for ArcStoredValue<T> {
@@ -49,7 +111,7 @@ impl<T: Default> Default for ArcStoredValue<T> {
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
-            value: Arc::new(RwLock::new(T::default())),
+            value: Arc::new(Slot::new(T::default())),
         }
     }
 }
@@ -92,7 +154,115 @@ impl<T> ArcStoredValue<T> {
         Self {
             #[cfg(any(debug_assertions, leptos_debuginfo))]
             defined_at: Location::caller(),
-            value: Arc::new(RwLock::new(value)),
+            value: Arc::new(Slot::new(value)),
+        }
+    }
+
+    /// Creates a new stored value that is built by calling `init` the first time it is accessed.
+    ///
+    /// `init` runs at most once, and later accesses use the value it returned. If the stored value
+    /// is dropped before it is ever accessed, `init` is dropped without being called.
+    ///
+    /// `init` must not access this stored value itself.
+    #[track_caller]
+    pub fn new_lazy(init: impl FnOnce() -> T + Send + 'static) -> Self {
+        Self {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+            value: Arc::new(Slot::new_lazy(Box::new(init))),
+        }
+    }
+
+    /// Returns the lock around the value, creating the value first if it is lazy and this is the
+    /// first access.
+    fn lock(&self) -> &Arc<RwLock<T>> {
+        self.value.value.get_or_init(|| {
+            let init = self
+                .value
+                .init
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .take()
+                .expect("the initializer of a lazy stored value panicked");
+            Arc::new(RwLock::new(init()))
+        })
+    }
+
+    /// Whether the value has been written to since it was created, or since the last call to
+    /// [`mark_clean`](Self::mark_clean).
+    ///
+    /// Every successful call to [`try_write_value`](WriteValue::try_write_value) marks the value
+    /// as dirty, whether or not it actually changes it. Reads don't.
+    pub fn dirty(&self) -> bool {
+        self.value.dirty.load(Ordering::Acquire)
+    }
+
+    /// Marks the value as clean, so that [`dirty`](Self::dirty) returns `false` until it is
+    /// written to again.
+    pub fn mark_clean(&self) {
+        self.value.dirty.store(false, Ordering::Release);
+    }
+
+    /// Replaces the stored value with `new` if it is currently equal to `expected`, returning
+    /// whether it was replaced.
+    ///
+    /// The comparison and the replacement happen under a single write lock, so no other update
+    /// can run in between. The value is only marked [`dirty`](Self::dirty) if it was replaced.
+    pub fn compare_and_set(&self, expected: &T, new: T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.recover_from_poison();
+        let mut value =
+            self.lock().write().unwrap_or_else(PoisonError::into_inner);
+        if *value != *expected {
+            return false;
+        }
+        *value = new;
+        self.value.dirty.store(true, Ordering::Release);
+        #[cfg(feature = "testing")]
+        self.value.accesses.writes.fetch_add(1, Ordering::Relaxed);
+        true
+    }
+
+    /// Clears the poison left on the lock by a closure that panicked while holding the value.
+    ///
+    /// The value itself is still valid (it was only ever handed out by reference), so one
+    /// panicking update shouldn't make every later access fail.
+    ///
+    /// The warning is only logged by the access that clears the poison, even if several threads
+    /// find the lock poisoned at the same time.
+    fn recover_from_poison(&self) {
+        let lock = self.lock();
+        if !lock.is_poisoned() {
+            return;
+        }
+        // no guard can be held on a poisoned lock, so this can't deadlock with the caller; once
+        // it is taken, no other access can clear the poison in between
+        let _guard = lock.write();
+        if lock.is_poisoned() {
+            lock.clear_poison();
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            crate::log_warning(format_args!(
+                "The stored value defined at {} was poisoned by a panic \
+                 while it was being accessed. Recovering the value as it was \
+                 left by the panicking closure.",
+                self.defined_at
+            ));
+        }
+    }
+
+    /// Returns the number of times the value has been read and written since it was created.
+    ///
+    /// Every successful call to [`try_read_value`](ReadValue::try_read_value) or
+    /// [`try_write_value`](WriteValue::try_write_value) is counted, including those made by the
+    /// methods built on them, like [`get_value`](crate::traits::GetValue::get_value) and
+    /// [`update_value`](crate::traits::UpdateValue::update_value).
+    #[cfg(feature = "testing")]
+    pub fn access_log(&self) -> AccessLog {
+        AccessLog {
+            reads: self.value.accesses.reads.load(Ordering::Relaxed),
+            writes: self.value.accesses.writes.load(Ordering::Relaxed),
         }
     }
 }
@@ -104,7 +274,13 @@ where
     type Value = ReadGuard<T, Plain<T>>;
 
     fn try_read_value(&self) -> Option<ReadGuard<T, Plain<T>>> {
-        Plain::try_new(Arc::clone(&self.value)).map(ReadGuard::new)
+        self.recover_from_poison();
+        let guard = Plain::try_new(Arc::clone(self.lock())).map(ReadGuard::new);
+        #[cfg(feature = "testing")]
+        if guard.is_some() {
+            self.value.accesses.reads.fetch_add(1, Ordering::Relaxed);
+        }
+        guard
     }
 }
 
@@ -115,7 +291,14 @@ where
     type Value = T;
 
     fn try_write_value(&self) -> Option<UntrackedWriteGuard<T>> {
-        UntrackedWriteGuard::try_new(self.value.clone())
+        self.recover_from_poison();
+        let guard = UntrackedWriteGuard::try_new(self.lock().clone());
+        if guard.is_some() {
+            self.value.dirty.store(true, Ordering::Release);
+            #[cfg(feature = "testing")]
+            self.value.accesses.writes.fetch_add(1, Ordering::Relaxed);
+        }
+        guard
     }
 }
 
@@ -130,6 +313,17 @@ impl<T> IntoInner for ArcStoredValue<T> {
 
     #[inline(always)]
     fn into_inner(self) -> Option<Self::Value> {
-        Some(Arc::into_inner(self.value)?.into_inner().unwrap())
+        let Slot { value, init, .. } = Arc::into_inner(self.value)?;
+        match value.into_inner() {
+            Some(lock) => Some(
+                Arc::into_inner(lock)?
+                    .into_inner()
+                    .unwrap_or_else(PoisonError::into_inner),
+            ),
+            None => init
+                .into_inner()
+                .unwrap_or_else(PoisonError::into_inner)
+                .map(|init| init()),
+        }
     }
 }
@@ -0,0 +1,69 @@
+use super::{arc_stored_value::ArcStoredValue, Storage, StoredValue};
+use crate::{
+    signal::guards::{Mapped, Plain, ReadGuard},
+    traits::{DefinedAt, IsDisposed, ReadValue},
+};
+use std::{
+    fmt::{Debug, Formatter},
+    panic::Location,
+};
+
+/// A **non-reactive**, `Copy`, read-only handle for part of a [`StoredValue`].
+///
+/// This is created with [`StoredValue::map`]. Reading it borrows the original value and applies
+/// the mapping function, so it always sees the current value, without cloning the rest of it.
+pub struct MappedStoredValue<T, U, S> {
+    inner: StoredValue<T, S>,
+    map: fn(&T) -> &U,
+}
+
+impl<T, U, S> MappedStoredValue<T, U, S> {
+    pub(super) fn new(inner: StoredValue<T, S>, map: fn(&T) -> &U) -> Self {
+        Self { inner, map }
+    }
+}
+
+impl<T, U, S> Copy for MappedStoredValue<T, U, S> {}
+
+impl<T, U, S> Clone for MappedStoredValue<T, U, S> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, U, S> Debug for MappedStoredValue<T, U, S>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MappedStoredValue")
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl<T, U, S> DefinedAt for MappedStoredValue<T, U, S> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.inner.defined_at()
+    }
+}
+
+impl<T, U, S> IsDisposed for MappedStoredValue<T, U, S> {
+    fn is_disposed(&self) -> bool {
+        self.inner.is_disposed()
+    }
+}
+
+impl<T, U, S> ReadValue for MappedStoredValue<T, U, S>
+where
+    T: 'static,
+    S: Storage<ArcStoredValue<T>>,
+{
+    type Value = Mapped<ReadGuard<T, Plain<T>>, U>;
+
+    fn try_read_value(&self) -> Option<Self::Value> {
+        self.inner
+            .try_read_value()
+            .map(|guard| Mapped::new_with_guard(guard, self.map))
+    }
+}
@@ -1,5 +1,7 @@
 use super::arena::{Arena, NodeId};
 use send_wrapper::SendWrapper;
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+use std::panic::Location;
 
 /// A trait for borrowing and taking data.
 pub trait StorageAccess<T> {
@@ -43,16 +45,19 @@ pub trait Storage<T>: Send + Sync + 'static {
 
     /// Applies the given function to the stored value, if it exists and can be accessed from this
     /// thread.
+    #[track_caller]
     fn try_with<U>(node: NodeId, fun: impl FnOnce(&T) -> U) -> Option<U>;
 
     /// Applies the given function to a mutable reference to the stored value, if it exists and can be accessed from this
     /// thread.
+    #[track_caller]
     fn try_with_mut<U>(
         node: NodeId,
         fun: impl FnOnce(&mut T) -> U,
     ) -> Option<U>;
 
     /// Sets a new value for the stored value. If it has been disposed, returns `Some(T)`.
+    #[track_caller]
     fn try_set(node: NodeId, value: T) -> Option<T>;
 
     /// Takes an item from the arena if it exists and can be accessed from this thread.
@@ -134,7 +139,11 @@ where
         SendWrapper::new(value)
     }
 
+    #[track_caller]
     fn try_with<U>(node: NodeId, fun: impl FnOnce(&T) -> U) -> Option<U> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        check_thread_affinity::<T>(node);
+
         Arena::with(|arena| {
             let m = arena.get(node);
             m.and_then(|n| n.downcast_ref::<SendWrapper<T>>())
@@ -142,10 +151,14 @@ where
         })
     }
 
+    #[track_caller]
     fn try_with_mut<U>(
         node: NodeId,
         fun: impl FnOnce(&mut T) -> U,
     ) -> Option<U> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        check_thread_affinity::<T>(node);
+
         Arena::with_mut(|arena| {
             let m = arena.get_mut(node);
             m.and_then(|n| n.downcast_mut::<SendWrapper<T>>())
@@ -153,7 +166,11 @@ where
         })
     }
 
+    #[track_caller]
     fn try_set(node: NodeId, value: T) -> Option<T> {
+        #[cfg(any(debug_assertions, leptos_debuginfo))]
+        check_thread_affinity::<T>(node);
+
         Arena::with_mut(|arena| {
             let m = arena.get_mut(node);
             match m.and_then(|n| n.downcast_mut::<SendWrapper<T>>()) {
@@ -176,3 +193,31 @@ where
         })
     }
 }
+
+/// Panics with a descriptive message if a value stored with [`LocalStorage`] is accessed from a
+/// thread other than the one on which it was created.
+///
+/// The check happens before the arena is locked for writing, so that the panic does not poison
+/// the arena for every other reactive value.
+#[cfg(any(debug_assertions, leptos_debuginfo))]
+#[track_caller]
+fn check_thread_affinity<T: 'static>(node: NodeId) {
+    let valid = Arena::try_with(|arena| {
+        arena
+            .get(node)
+            .and_then(|n| n.downcast_ref::<SendWrapper<T>>())
+            .map(SendWrapper::valid)
+    })
+    .flatten();
+    if valid == Some(false) {
+        panic!(
+            "At {}, you tried to access a value of type `{}` that was stored \
+             with `LocalStorage` from a different thread than the one on \
+             which it was created. Values stored with `LocalStorage` (for \
+             example, with `StoredValue::new_local`) are not `Send` and can \
+             only be accessed from their original thread.",
+            Location::caller(),
+            std::any::type_name::<T>()
+        );
+    }
+}
@@ -0,0 +1,133 @@
+use super::StoredValue;
+use crate::traits::{DefinedAt, Dispose, GetValue, IsDisposed};
+use async_lock::OnceCell;
+use std::{
+    fmt::{Debug, Formatter},
+    future::Future,
+    panic::Location,
+    sync::Arc,
+};
+
+/// The state of a [`StoredOnce`]: either empty, or holding a value that has been computed.
+///
+/// The cell is shared, so that callers can wait for the computation without holding a lock on
+/// the stored value.
+pub struct OnceState<T>(Arc<OnceCell<T>>);
+
+impl<T> Clone for OnceState<T> {
+    fn clone(&self) -> Self {
+        Self(Arc::clone(&self.0))
+    }
+}
+
+impl<T> Default for OnceState<T> {
+    fn default() -> Self {
+        Self(Arc::new(OnceCell::new()))
+    }
+}
+
+impl<T> Debug for OnceState<T>
+where
+    T: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("OnceState").field(&self.0.get()).finish()
+    }
+}
+
+/// A **non-reactive**, `Copy` handle for a value that is computed asynchronously, at most once.
+///
+/// The first call to [`get_or_init_async`](StoredOnce::get_or_init_async) runs its future and
+/// caches the result. Any calls made while that future is running wait for it to finish,
+/// rather than starting their own computation, and later calls return the cached value.
+pub struct StoredOnce<T> {
+    value: StoredValue<OnceState<T>>,
+}
+
+impl<T> Copy for StoredOnce<T> {}
+
+impl<T> Clone for StoredOnce<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Debug for StoredOnce<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoredOnce")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T> DefinedAt for StoredOnce<T> {
+    fn defined_at(&self) -> Option<&'static Location<'static>> {
+        self.value.defined_at()
+    }
+}
+
+impl<T> IsDisposed for StoredOnce<T> {
+    fn is_disposed(&self) -> bool {
+        self.value.is_disposed()
+    }
+}
+
+impl<T> Dispose for StoredOnce<T> {
+    fn dispose(self) {
+        self.value.dispose();
+    }
+}
+
+impl<T> Default for StoredOnce<T>
+where
+    T: Send + Sync + 'static,
+{
+    #[track_caller]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> StoredOnce<T>
+where
+    T: Send + Sync + 'static,
+{
+    /// Creates a new, empty value in the arena allocator.
+    #[track_caller]
+    pub fn new() -> Self {
+        Self {
+            value: StoredValue::new(OnceState::default()),
+        }
+    }
+
+    /// Returns the value, if it has already been computed.
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[track_caller]
+    pub fn get(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.value.get_value().0.get().cloned()
+    }
+
+    /// Returns the value, running the future to compute it if this is the first call.
+    ///
+    /// If another call is already running its future, this waits for that one to finish and
+    /// returns its result, without polling `fut`.
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[track_caller]
+    pub fn get_or_init_async(
+        &self,
+        fut: impl Future<Output = T>,
+    ) -> impl Future<Output = T>
+    where
+        T: Clone,
+    {
+        let OnceState(cell) = self.value.get_value();
+        async move { cell.get_or_init(|| fut).await.clone() }
+    }
+}
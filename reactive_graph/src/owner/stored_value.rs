@@ -1,17 +1,23 @@
 use super::{
-    arc_stored_value::ArcStoredValue, ArenaItem, LocalStorage, Storage,
-    SyncStorage,
+    arc_stored_value::ArcStoredValue, ArenaItem, LocalStorage,
+    MappedStoredValue, Owner, Storage, SyncStorage,
 };
 use crate::{
-    signal::guards::{Plain, ReadGuard, UntrackedWriteGuard},
+    signal::{
+        guards::{Plain, ReadGuard, UntrackedWriteGuard},
+        ArcRwSignal, ReadSignal,
+    },
     traits::{
-        DefinedAt, Dispose, IntoInner, IsDisposed, ReadValue, WriteValue,
+        DefinedAt, Dispose, IntoInner, IsDisposed, ReadValue, WithValue,
+        WriteValue,
     },
     unwrap_signal,
 };
 use std::{
+    borrow::Cow,
     fmt::{Debug, Formatter},
     hash::Hash,
+    mem,
     panic::Location,
 };
 
@@ -89,6 +95,225 @@ where
             defined_at: Location::caller(),
         }
     }
+
+    /// Creates a read-only handle for part of the stored value.
+    ///
+    /// The handle is `Copy`, and reading it does not clone the rest of the value. Because it
+    /// borrows the original value each time it is read, it observes any later updates.
+    ///
+    /// # Panics
+    /// Reading the handle panics if the original value has been disposed; use the `try_`
+    /// methods to handle that case instead.
+    pub fn map<U>(self, map: fn(&T) -> &U) -> MappedStoredValue<T, U, S> {
+        MappedStoredValue::new(self, map)
+    }
+
+    /// Clones the current value, or returns `None` if it cannot be read right now.
+    ///
+    /// Unlike [`get_value`](crate::traits::GetValue::get_value), this never panics. In
+    /// particular, it is safe to call while the value is being updated (for example, from inside
+    /// an [`update_value`](crate::traits::UpdateValue::update_value) closure): no copy of the old
+    /// value is kept, so it returns `None` until the update has finished. It also returns `None`
+    /// if the value has been disposed.
+    #[track_caller]
+    pub fn snapshot(&self) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.try_read_value().map(|value| T::clone(&value))
+    }
+
+    /// Replaces the stored value, returning the previous value, or hands the new value back in
+    /// `Err` if the value has been disposed.
+    #[track_caller]
+    pub fn try_replace_value(&self, value: T) -> Result<T, T> {
+        match self.try_write_value() {
+            Some(mut guard) => Ok(mem::replace(&mut *guard, value)),
+            None => Err(value),
+        }
+    }
+
+    /// Replaces the stored value, returning the previous value.
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[track_caller]
+    pub fn replace_value(&self, value: T) -> T {
+        match self.try_replace_value(value) {
+            Ok(prev) => prev,
+            Err(_) => unwrap_signal!(self)(),
+        }
+    }
+
+    /// Moves the value out, leaving [`T::default()`](Default::default) in its place, or returns
+    /// `None` if the value has been disposed.
+    ///
+    /// Unlike [`dispose`](Dispose::dispose), this leaves the value usable, so calling it again
+    /// returns the default value.
+    #[track_caller]
+    pub fn try_take_value(&self) -> Option<T>
+    where
+        T: Default,
+    {
+        self.try_write_value()
+            .map(|mut guard| mem::take(&mut *guard))
+    }
+
+    /// Moves the value out, leaving [`T::default()`](Default::default) in its place.
+    ///
+    /// Unlike [`dispose`](Dispose::dispose), this leaves the value usable, so calling it again
+    /// returns the default value.
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[track_caller]
+    pub fn take_value(&self) -> T
+    where
+        T: Default,
+    {
+        self.try_take_value().unwrap_or_else(unwrap_signal!(self))
+    }
+
+    /// Swaps the contents of this value with another one, without cloning either.
+    ///
+    /// Returns `false`, without changing either value, if either has been disposed. Swapping a
+    /// value with itself does nothing.
+    #[track_caller]
+    pub fn try_swap_value(&self, other: &Self) -> bool {
+        if self == other {
+            return !self.is_disposed();
+        }
+        match (self.try_write_value(), other.try_write_value()) {
+            (Some(mut a), Some(mut b)) => {
+                mem::swap(&mut *a, &mut *b);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Swaps the contents of this value with another one, without cloning either.
+    ///
+    /// Swapping a value with itself does nothing.
+    ///
+    /// # Panics
+    /// Panics if either value has been disposed.
+    #[track_caller]
+    pub fn swap_value(&self, other: &Self) {
+        if !self.try_swap_value(other) {
+            let disposed = if self.is_disposed() { self } else { other };
+            unwrap_signal!(disposed)();
+        }
+    }
+
+    /// Replaces the stored value with `new` if it is currently equal to `expected`, returning
+    /// whether it was replaced.
+    ///
+    /// The comparison and the replacement happen under a single write lock, so no other update
+    /// can run in between. The value is only marked [`dirty`](Self::dirty) if it was replaced.
+    /// Returns `false` if the value has been disposed.
+    #[track_caller]
+    pub fn compare_and_set(&self, expected: &T, new: T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.value
+            .try_get_value()
+            .is_some_and(|value| value.compare_and_set(expected, new))
+    }
+
+    /// Returns a value that formats the stored value with [`Debug`], or `<disposed>` if it has
+    /// been disposed.
+    ///
+    /// The [`Debug`] implementation for `StoredValue` itself only shows the handle.
+    pub fn debug_value(&self) -> impl Debug + '_
+    where
+        T: Debug,
+    {
+        struct DebugValue<'a, T: 'static, S>(&'a StoredValue<T, S>);
+
+        impl<T, S> Debug for DebugValue<'_, T, S>
+        where
+            T: Debug + 'static,
+            S: Storage<ArcStoredValue<T>>,
+        {
+            fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+                match self.0.try_read_value() {
+                    Some(value) => Debug::fmt(&*value, f),
+                    None => f.write_str("<disposed>"),
+                }
+            }
+        }
+
+        DebugValue(self)
+    }
+}
+
+impl<T, S> StoredValue<T, S>
+where
+    T: 'static,
+    S: Storage<ArcStoredValue<T>>,
+{
+    /// Whether the value may have changed since it was created, or since the last call to
+    /// [`mark_clean`](Self::mark_clean).
+    ///
+    /// Any write access marks the value as dirty, including [`set_value`](crate::traits::SetValue::set_value)
+    /// and [`update_value`](crate::traits::UpdateValue::update_value), even if the new value is
+    /// the same as the old one. Reading the value does not. This is useful when syncing the value
+    /// to an external store, like a database or `localStorage`:
+    /// ```rust
+    /// # use reactive_graph::owner::StoredValue;
+    /// # use reactive_graph::traits::UpdateValue;
+    /// let draft = StoredValue::new(String::new());
+    /// draft.update_value(|text| text.push_str("hello"));
+    ///
+    /// if draft.dirty() {
+    ///     // save the draft here...
+    ///     draft.mark_clean();
+    /// }
+    /// assert!(!draft.dirty());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[track_caller]
+    pub fn dirty(&self) -> bool {
+        self.value
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(self))
+            .dirty()
+    }
+
+    /// Marks the value as clean, so that [`dirty`](Self::dirty) returns `false` until it is
+    /// written to again.
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[track_caller]
+    pub fn mark_clean(&self) {
+        self.value
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(self))
+            .mark_clean();
+    }
+
+    /// Returns the number of times the value has been read and written since it was created.
+    ///
+    /// This lets tests check, for example, that a callback updated the value exactly twice.
+    /// Every read or write that reaches the value is counted, including those made through
+    /// helpers like [`get_value`](crate::traits::GetValue::get_value) and
+    /// [`update_value`](crate::traits::UpdateValue::update_value).
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[cfg(feature = "testing")]
+    #[track_caller]
+    pub fn access_log(&self) -> super::AccessLog {
+        self.value
+            .try_get_value()
+            .unwrap_or_else(unwrap_signal!(self))
+            .access_log()
+    }
 }
 
 impl<T, S> Default for StoredValue<T, S>
@@ -111,6 +336,58 @@ where
     pub fn new(value: T) -> Self {
         StoredValue::new_with_storage(value)
     }
+
+    /// Stores the given value in the arena allocator, owned by `owner` rather than by the
+    /// current owner.
+    ///
+    /// The value is disposed when `owner` is cleaned up, which is useful for values created by
+    /// long-lived tasks that run outside of the reactive tree.
+    #[track_caller]
+    pub fn new_in(owner: &Owner, value: T) -> Self {
+        Self {
+            value: owner.with(|| {
+                ArenaItem::new_with_storage(ArcStoredValue::new(value))
+            }),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+        }
+    }
+
+    /// Creates a [`ReadSignal`] that holds a copy of the current value.
+    ///
+    /// This is a one-time snapshot, for handing the value to reactive code: the signal is not
+    /// linked to this stored value, so later calls to
+    /// [`update_value`](crate::traits::UpdateValue::update_value) or
+    /// [`set_value`](crate::traits::SetValue::set_value) do **not** change it, and nothing that
+    /// reads the signal is notified of them.
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[track_caller]
+    pub fn to_read_signal(&self) -> ReadSignal<T>
+    where
+        T: Clone,
+    {
+        ArcRwSignal::new(self.with_value(T::clone))
+            .read_only()
+            .into()
+    }
+
+    /// Stores a value that is built by calling `init` the first time it is accessed, by
+    /// [`with_value`](crate::traits::WithValue::with_value),
+    /// [`update_value`](crate::traits::UpdateValue::update_value), or any other method.
+    ///
+    /// This is for state that is expensive to construct and may never be used. `init` runs at
+    /// most once; if the value is disposed before it is accessed, `init` is dropped without
+    /// being called.
+    #[track_caller]
+    pub fn new_lazy(init: impl FnOnce() -> T + Send + 'static) -> Self {
+        Self {
+            value: ArenaItem::new_with_storage(ArcStoredValue::new_lazy(init)),
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: Location::caller(),
+        }
+    }
 }
 
 impl<T> StoredValue<T, LocalStorage>
@@ -124,6 +401,29 @@ where
     }
 }
 
+impl<S> StoredValue<Cow<'static, str>, S>
+where
+    S: Storage<ArcStoredValue<Cow<'static, str>>>,
+{
+    /// Clones the stored string into an owned [`String`].
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[track_caller]
+    pub fn get_str(&self) -> String {
+        self.with_value(|value| value.to_string())
+    }
+
+    /// Applies the closure to the stored string, without cloning it.
+    ///
+    /// # Panics
+    /// Panics if you try to access a value that has been disposed.
+    #[track_caller]
+    pub fn with_str<O>(&self, fun: impl FnOnce(&str) -> O) -> O {
+        self.with_value(|value| fun(value))
+    }
+}
+
 impl<T, S> ReadValue for StoredValue<T, S>
 where
     T: 'static,
@@ -648,6 +648,12 @@ where
 pub trait IsDisposed {
     /// If `true`, the signal cannot be accessed without a panic.
     fn is_disposed(&self) -> bool;
+
+    /// The inverse of [`is_disposed`](IsDisposed::is_disposed): if `true`, the signal can still
+    /// be accessed.
+    fn is_alive(&self) -> bool {
+        !self.is_disposed()
+    }
 }
 
 /// Turns a signal back into a raw value.
@@ -814,10 +820,14 @@ pub trait UpdateValue: DefinedAt {
         fun: impl FnOnce(&mut Self::Value) -> U,
     ) -> Option<U>;
 
-    /// Updates the value.
+    /// Updates the value, returning the value that is returned by the update function.
+    ///
+    /// # Panics
+    /// Panics if you try to update a value that has been disposed.
     #[track_caller]
-    fn update_value(&self, fun: impl FnOnce(&mut Self::Value)) {
-        self.try_update_value(fun);
+    fn update_value<U>(&self, fun: impl FnOnce(&mut Self::Value) -> U) -> U {
+        self.try_update_value(fun)
+            .unwrap_or_else(unwrap_signal!(self))
     }
 }
 
@@ -1,10 +1,10 @@
 use reactive_graph::{
     computed::Memo,
-    owner::{on_cleanup, Owner},
+    owner::{on_cleanup, Owner, StoredValue},
     signal::{RwSignal, Trigger},
     traits::{Dispose, GetUntracked, Track},
 };
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 #[test]
 fn cleanup_on_dispose() {
@@ -86,3 +86,30 @@ fn leak_on_dispose() {
 
     assert!(weak.upgrade().is_none()); // Should have been dropped.
 }
+
+#[test]
+fn arena_values_drop_in_reverse_creation_order() {
+    struct LogOnDrop(&'static str, Arc<Mutex<Vec<&'static str>>>);
+
+    impl Drop for LogOnDrop {
+        fn drop(&mut self) {
+            self.1.lock().unwrap().push(self.0);
+        }
+    }
+
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let owner = Owner::new();
+    owner.with(|| {
+        _ = RwSignal::new(LogOnDrop("signal a", Arc::clone(&log)));
+        _ = StoredValue::new(LogOnDrop("stored b", Arc::clone(&log)));
+        _ = RwSignal::new(LogOnDrop("signal c", Arc::clone(&log)));
+        _ = StoredValue::new(LogOnDrop("stored d", Arc::clone(&log)));
+    });
+    assert!(log.lock().unwrap().is_empty());
+
+    owner.cleanup();
+    assert_eq!(
+        *log.lock().unwrap(),
+        ["stored d", "signal c", "stored b", "signal a"]
+    );
+}
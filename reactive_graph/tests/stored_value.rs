@@ -0,0 +1,401 @@
+use reactive_graph::{
+    owner::{Owner, StoredOnce, StoredValue},
+    traits::{Dispose, GetValue, IsDisposed, UpdateValue, WithValue},
+};
+use std::{
+    borrow::Cow,
+    panic::AssertUnwindSafe,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+#[cfg(debug_assertions)]
+#[test]
+fn local_stored_value_panics_on_other_thread() {
+    let value = StoredValue::new_local(42);
+    assert_eq!(value.get_value(), 42);
+
+    let err = std::thread::spawn(move || value.get_value())
+        .join()
+        .unwrap_err();
+    let msg = err.downcast_ref::<String>().unwrap();
+    assert!(msg.contains("from a different thread"));
+    assert!(msg.contains("i32"));
+
+    // the arena is still usable from the original thread
+    assert_eq!(value.get_value(), 42);
+}
+
+#[test]
+fn cow_stored_value_get_str() {
+    let borrowed = StoredValue::new(Cow::Borrowed("hello"));
+    let owned = StoredValue::new(Cow::<'static, str>::Owned("world".into()));
+    assert_eq!(borrowed.get_str(), "hello");
+    assert_eq!(owned.get_str(), "world");
+}
+
+#[test]
+fn cow_stored_value_with_str() {
+    let borrowed = StoredValue::new(Cow::Borrowed("hello"));
+    let owned = StoredValue::new(Cow::<'static, str>::Owned("world".into()));
+    assert_eq!(borrowed.with_str(str::len), 5);
+    assert!(owned.with_str(|s| s.starts_with("wor")));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn update_value_panic_points_to_call_site() {
+    let value = StoredValue::new_local(0);
+    let call_site = format!("{}:{}:", file!(), line!() + 1);
+    let err = std::thread::spawn(move || value.update_value(|n| *n += 1))
+        .join()
+        .unwrap_err();
+    let msg = err.downcast_ref::<String>().unwrap();
+    assert!(
+        msg.contains(&call_site),
+        "expected panic at {call_site}, got: {msg}"
+    );
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn update_disposed_value_panic_points_to_call_site() {
+    let value = StoredValue::new(0);
+    value.dispose();
+    let call_site = format!("{}:{}:", file!(), line!() + 2);
+    let err = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        value.update_value(|n| *n += 1)
+    }))
+    .unwrap_err();
+    let msg = err.downcast_ref::<String>().unwrap();
+    assert!(msg.contains("disposed"), "{msg}");
+    assert!(
+        msg.contains(&call_site),
+        "expected panic at {call_site}, got: {msg}"
+    );
+}
+
+#[tokio::test]
+async fn stored_once_runs_future_once() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let once = StoredOnce::new();
+    let init = || async {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        // let the other callers start waiting while this is still pending
+        tokio::task::yield_now().await;
+        42
+    };
+
+    assert_eq!(once.get(), None);
+    let (a, b, c) = futures::join!(
+        once.get_or_init_async(init()),
+        once.get_or_init_async(init()),
+        once.get_or_init_async(init()),
+    );
+    assert_eq!((a, b, c), (42, 42, 42));
+    assert_eq!(once.get_or_init_async(init()).await, 42);
+    assert_eq!(once.get(), Some(42));
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn mapped_stored_value_observes_updates() {
+    struct Config {
+        name: String,
+        retries: usize,
+    }
+
+    let config = StoredValue::new(Config {
+        name: "first".to_string(),
+        retries: 3,
+    });
+    let name = config.map(|config| &config.name);
+    assert_eq!(name.get_value(), "first");
+
+    config.update_value(|config| config.name = "second".to_string());
+    assert_eq!(name.get_value(), "second");
+    assert_eq!(name.with_value(String::len), 6);
+    assert_eq!(config.with_value(|config| config.retries), 3);
+
+    config.dispose();
+    assert_eq!(name.try_get_value(), None);
+}
+
+#[test]
+fn update_value_returns_closure_result() {
+    let value = StoredValue::new(vec![1, 2]);
+    let len = value.update_value(|v| {
+        v.push(3);
+        v.len()
+    });
+    assert_eq!(len, 3);
+    assert_eq!(value.update_value(|v| v.pop()), Some(3));
+}
+
+#[test]
+fn snapshot_during_update_returns_none() {
+    let value = StoredValue::new(1);
+    let mut during = Some(0);
+    value.update_value(|n| {
+        during = value.snapshot();
+        *n += 1;
+    });
+    assert_eq!(during, None);
+    assert_eq!(value.snapshot(), Some(2));
+
+    value.dispose();
+    assert_eq!(value.snapshot(), None);
+}
+
+#[test]
+fn replace_value_returns_previous() {
+    let widgets = StoredValue::new(vec![1, 2, 3]);
+    assert_eq!(widgets.replace_value(vec![4]), vec![1, 2, 3]);
+    assert_eq!(widgets.try_replace_value(vec![5, 6]), Ok(vec![4]));
+    assert_eq!(widgets.get_value(), vec![5, 6]);
+}
+
+#[test]
+fn try_replace_disposed_value_returns_new_value() {
+    let widgets = StoredValue::new(vec![1, 2, 3]);
+    widgets.dispose();
+    assert_eq!(widgets.try_replace_value(vec![4]), Err(vec![4]));
+}
+
+#[test]
+fn take_value_leaves_default() {
+    let value = StoredValue::new(String::from("hello"));
+    assert_eq!(value.take_value(), "hello");
+    assert_eq!(value.take_value(), "");
+    assert_eq!(value.try_take_value(), Some(String::new()));
+
+    value.dispose();
+    assert_eq!(value.try_take_value(), None);
+}
+
+#[test]
+fn stored_value_is_alive_until_owner_cleanup() {
+    // does not implement Clone, so it cannot be checked with try_get_value
+    struct Handle;
+
+    let owner = Owner::new();
+    let value = owner.with(|| StoredValue::new(Handle));
+    assert!(value.is_alive());
+    assert!(!value.is_disposed());
+
+    owner.cleanup();
+    assert!(!value.is_alive());
+    assert!(value.is_disposed());
+}
+
+#[test]
+fn stored_value_in_owner_is_disposed_with_that_owner() {
+    let a = Owner::new();
+    let b = Owner::new();
+    let in_a = StoredValue::new_in(&a, 1);
+    let in_b = StoredValue::new_in(&b, 2);
+
+    a.cleanup();
+    assert!(in_a.is_disposed());
+    assert_eq!(in_b.get_value(), 2);
+}
+
+#[test]
+fn stored_value_in_child_scope_is_disposed_when_it_exits() {
+    let parent = Owner::new();
+    let outer = parent.with(|| StoredValue::new(1));
+    let child = parent.child();
+    let scoped = StoredValue::new_in(&child, 2);
+
+    child.cleanup();
+    assert!(scoped.is_disposed());
+    assert_eq!(outer.get_value(), 1);
+}
+
+#[test]
+fn lazy_stored_value_initializes_once() {
+    static RUNS: AtomicUsize = AtomicUsize::new(0);
+    let value = StoredValue::new_lazy(|| {
+        RUNS.fetch_add(1, Ordering::SeqCst);
+        vec![1, 2]
+    });
+    assert_eq!(RUNS.load(Ordering::SeqCst), 0);
+
+    assert_eq!(value.get_value(), vec![1, 2]);
+    value.update_value(|v| v.push(3));
+    assert_eq!(value.with_value(Vec::len), 3);
+    assert_eq!(RUNS.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+fn lazy_stored_value_disposed_before_access_drops_initializer() {
+    let owner = Owner::new();
+    let captured = std::sync::Arc::new(());
+    let in_init = std::sync::Arc::clone(&captured);
+    let value = owner.with(|| {
+        StoredValue::<i32>::new_lazy(move || {
+            let _ = &in_init;
+            panic!("initializer should not run");
+        })
+    });
+
+    owner.cleanup();
+    assert!(value.is_disposed());
+    assert_eq!(std::sync::Arc::strong_count(&captured), 1);
+}
+
+#[test]
+fn read_signal_is_a_snapshot_of_the_stored_value() {
+    use reactive_graph::traits::{GetUntracked, SetValue};
+
+    let value = StoredValue::new(vec![1, 2]);
+    let snapshot = value.to_read_signal();
+    value.set_value(vec![3]);
+    assert_eq!(snapshot.get_untracked(), vec![1, 2]);
+    assert_eq!(value.get_value(), vec![3]);
+}
+
+#[test]
+fn write_guard_mutates_across_statements() {
+    use reactive_graph::traits::WriteValue;
+
+    let value = StoredValue::new(Vec::new());
+    {
+        let mut guard = value.try_write_value().unwrap();
+        guard.push(1);
+        guard.push(2);
+        guard.retain(|n| n % 2 == 0);
+    }
+    // the borrow was released when the guard was dropped
+    assert_eq!(value.get_value(), vec![2]);
+
+    value.dispose();
+    assert!(value.try_write_value().is_none());
+}
+
+#[test]
+fn swap_value_exchanges_contents() {
+    let front = StoredValue::new(vec![1, 2]);
+    let back = StoredValue::new(vec![3]);
+    front.swap_value(&back);
+    assert_eq!(front.get_value(), vec![3]);
+    assert_eq!(back.get_value(), vec![1, 2]);
+}
+
+#[test]
+fn swap_value_with_itself_does_nothing() {
+    let value = StoredValue::new(vec![1, 2]);
+    let same = value;
+    value.swap_value(&same);
+    assert_eq!(value.get_value(), vec![1, 2]);
+}
+
+#[test]
+fn try_swap_disposed_value_returns_false() {
+    let a = StoredValue::new(1);
+    let b = StoredValue::new(2);
+    b.dispose();
+    assert!(!a.try_swap_value(&b));
+    assert!(!b.try_swap_value(&b));
+    assert_eq!(a.get_value(), 1);
+}
+
+#[test]
+fn compare_and_set_replaces_matching_value() {
+    let value = StoredValue::new(1);
+    assert!(value.compare_and_set(&1, 2));
+    assert_eq!(value.get_value(), 2);
+}
+
+#[test]
+fn compare_and_set_keeps_mismatched_value() {
+    let value = StoredValue::new(1);
+    assert!(!value.compare_and_set(&3, 2));
+    assert_eq!(value.get_value(), 1);
+}
+
+#[test]
+fn compare_and_set_only_marks_dirty_when_replacing() {
+    let value = StoredValue::new(1);
+    value.mark_clean();
+    assert!(!value.compare_and_set(&3, 2));
+    assert!(!value.dirty());
+    assert!(value.compare_and_set(&1, 2));
+    assert!(value.dirty());
+}
+
+#[test]
+fn compare_and_set_on_disposed_value_returns_false() {
+    let value = StoredValue::new(1);
+    value.dispose();
+    assert!(!value.compare_and_set(&1, 2));
+}
+
+#[test]
+fn debug_value_prints_contents() {
+    let value = StoredValue::new(42);
+    assert_eq!(format!("{:?}", value.debug_value()), "42");
+
+    value.dispose();
+    assert_eq!(format!("{:?}", value.debug_value()), "<disposed>");
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn stored_value_access_log_counts_reads_and_writes() {
+    use reactive_graph::{owner::AccessLog, traits::SetValue};
+
+    let value = StoredValue::new(0);
+    assert_eq!(value.access_log(), AccessLog::default());
+
+    let increment = move || value.update_value(|n| *n += 1);
+    increment();
+    increment();
+    assert_eq!(value.get_value(), 2);
+    value.with_value(|n| assert_eq!(*n, 2));
+    assert_eq!(
+        value.access_log(),
+        AccessLog {
+            reads: 2,
+            writes: 2
+        }
+    );
+
+    value.set_value(5);
+    assert_eq!(value.access_log().writes, 3);
+}
+
+#[test]
+fn stored_value_tracks_dirty_until_marked_clean() {
+    use reactive_graph::traits::SetValue;
+
+    let value = StoredValue::new(0);
+    assert!(!value.dirty());
+
+    value.update_value(|n| *n += 1);
+    assert!(value.dirty());
+
+    value.mark_clean();
+    assert!(!value.dirty());
+    assert_eq!(value.get_value(), 1);
+    value.with_value(|n| assert_eq!(*n, 1));
+    assert!(!value.dirty());
+
+    value.set_value(5);
+    assert!(value.dirty());
+}
+
+#[test]
+fn stored_value_recovers_after_panicking_update() {
+    let value = StoredValue::new(vec![1]);
+    let panicked = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        value.update_value(|v| {
+            v.push(2);
+            panic!("update failed halfway");
+        })
+    }));
+    assert!(panicked.is_err());
+
+    // the lock was poisoned, but the value is still accessible as the closure left it
+    assert_eq!(value.try_update_value(|v| v.push(3)), Some(()));
+    assert_eq!(value.try_with_value(Vec::clone), Some(vec![1, 2, 3]));
+}
@@ -0,0 +1,70 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, RwLock,
+};
+
+type Observer = Arc<dyn Fn(&LifecycleEvent<'_>) + Send + Sync>;
+
+static OBSERVERS: RwLock<Vec<(ObserverId, Observer)>> = RwLock::new(Vec::new());
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// The stage in an element's lifecycle that an observer is notified about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LifecycleKind {
+    /// The element was created, without hydrating existing HTML.
+    Build,
+    /// The element was inserted into a parent.
+    Mount,
+    /// The element was removed from its parent.
+    Unmount,
+    /// The element was hydrated from existing HTML.
+    Hydrate,
+}
+
+/// An event sent to lifecycle observers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LifecycleEvent<'a> {
+    /// What happened to the element.
+    pub kind: LifecycleKind,
+    /// The element's tag name.
+    pub tag: &'a str,
+}
+
+/// Identifies an observer added with [`add_lifecycle_observer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObserverId(usize);
+
+/// Adds an observer that is notified whenever any element is built, mounted, unmounted, or
+/// hydrated.
+pub fn add_lifecycle_observer(
+    observer: impl Fn(&LifecycleEvent<'_>) + Send + Sync + 'static,
+) -> ObserverId {
+    let id = ObserverId(NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    OBSERVERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((id, Arc::new(observer)));
+    id
+}
+
+/// Removes an observer that was added with [`add_lifecycle_observer`].
+pub fn remove_lifecycle_observer(id: ObserverId) {
+    OBSERVERS
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .retain(|(observer_id, _)| *observer_id != id);
+}
+
+pub(crate) fn notify(kind: LifecycleKind, tag: &str) {
+    // clone the observers, so that they can add or remove observers themselves
+    let observers = OBSERVERS
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|(_, observer)| Arc::clone(observer))
+        .collect::<Vec<_>>();
+    let event = LifecycleEvent { kind, tag };
+    for observer in observers {
+        observer(&event);
+    }
+}
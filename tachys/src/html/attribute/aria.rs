@@ -6,6 +6,7 @@ use crate::{
     renderer::Rndr,
     view::{add_attr::AddAnyAttr, RenderHtml},
 };
+use std::{borrow::Cow, sync::Arc};
 
 /// Applies ARIA attributes to an HTML element.
 pub trait AriaAttributes<Rndr, V>
@@ -174,10 +175,16 @@ where
     }
 
     /// Indicates whether the element is exposed to an accessibility API.
+    ///
+    /// This takes a `bool`, or a function returning one: `true` renders `aria-hidden="true"`,
+    /// and `false` leaves the attribute out. A string is rendered as it is.
     fn aria_hidden(
         self,
         value: V,
-    ) -> <Self as AddAnyAttr>::Output<Attr<AriaHidden, V>> {
+    ) -> <Self as AddAnyAttr>::Output<Attr<AriaHidden, V::Output>>
+    where
+        V: IntoAriaFlag,
+    {
         self.add_any_attr(aria_hidden(value))
     }
 
@@ -398,6 +405,71 @@ where
     }
 }
 
+/// A value for an ARIA attribute that is either `"true"` or absent, like `aria-hidden`.
+///
+/// ARIA attributes are not HTML boolean attributes, so they can't be rendered like `disabled`:
+/// an empty `aria-hidden` is ignored by assistive technologies. Strings, like `"true"` or
+/// `"false"`, are already valid values and are rendered as they are.
+pub trait IntoAriaFlag {
+    /// The attribute value this converts into.
+    type Output: AttributeValue;
+
+    /// Converts `true` into `"true"`, and `false` into no attribute.
+    fn into_aria_flag(self) -> Self::Output;
+}
+
+impl IntoAriaFlag for bool {
+    type Output = Option<&'static str>;
+
+    fn into_aria_flag(self) -> Self::Output {
+        self.then_some("true")
+    }
+}
+
+impl IntoAriaFlag for &str {
+    type Output = Self;
+
+    fn into_aria_flag(self) -> Self::Output {
+        self
+    }
+}
+
+impl IntoAriaFlag for String {
+    type Output = Self;
+
+    fn into_aria_flag(self) -> Self::Output {
+        self
+    }
+}
+
+impl IntoAriaFlag for Cow<'_, str> {
+    type Output = Self;
+
+    fn into_aria_flag(self) -> Self::Output {
+        self
+    }
+}
+
+impl IntoAriaFlag for Arc<str> {
+    type Output = Self;
+
+    fn into_aria_flag(self) -> Self::Output {
+        self
+    }
+}
+
+#[cfg(feature = "reactive_graph")]
+impl<F> IntoAriaFlag for F
+where
+    F: Fn() -> bool + Send + Sync + 'static,
+{
+    type Output = Arc<dyn Fn() -> Option<&'static str> + Send + Sync>;
+
+    fn into_aria_flag(self) -> Self::Output {
+        Arc::new(move || self().into_aria_flag())
+    }
+}
+
 impl<El, At, Ch, V> AriaAttributes<Rndr, V> for HtmlElement<El, At, Ch>
 where
     El: ElementType + Send,
@@ -406,3 +478,59 @@ where
     V: AttributeValue,
 {
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::AriaAttributes;
+    use crate::{
+        html::{
+            attribute::{
+                aria_hidden, global::GlobalAttributes, keywords::AriaRole,
+            },
+            element::{button, span, ElementChild},
+        },
+        view::{add_attr::AddAnyAttr, RenderHtml},
+    };
+
+    #[test]
+    fn labelled_button() {
+        let html = button()
+            .aria_label("Close")
+            .aria_labelledby("dialog-title")
+            .child(span().aria_hidden(true).child("×"))
+            .to_html();
+        assert_eq!(
+            html,
+            "<button aria-label=\"Close\" aria-labelledby=\"dialog-title\"><span \
+             aria-hidden=\"true\">×</span></button>"
+        );
+    }
+
+    #[test]
+    fn aria_hidden_false_is_omitted() {
+        let html = span().aria_hidden(false).child("visible").to_html();
+        assert_eq!(html, "<span>visible</span>");
+    }
+
+    #[test]
+    fn aria_hidden_accepts_strings() {
+        let html = span().aria_hidden("true").to_html();
+        assert_eq!(html, r#"<span aria-hidden="true"></span>"#);
+        let html = span().aria_hidden(String::from("false")).to_html();
+        assert_eq!(html, r#"<span aria-hidden="false"></span>"#);
+    }
+
+    #[test]
+    fn attribute_fn_converts_like_the_builder() {
+        let html = span().add_any_attr(aria_hidden(true)).to_html();
+        assert_eq!(html, span().aria_hidden(true).to_html());
+        let html = span().add_any_attr(aria_hidden(false)).to_html();
+        assert_eq!(html, "<span></span>");
+    }
+
+    #[test]
+    fn typed_role() {
+        let html = span().role(AriaRole::Status).to_html();
+        assert_eq!(html, r#"<span role="status"></span>"#);
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
         maybe_next_attr_erasure_macros::next_attr_combine, Attribute,
         AttributeValue, NamedAttributeKey,
     },
+    ssr::{DefaultAttr, ElementOptions},
     view::{add_attr::AddAnyAttr, Position, ToTemplate},
 };
 use std::{borrow::Cow, sync::Arc};
@@ -20,6 +21,34 @@ where
     CustomAttr { key, value }
 }
 
+/// Adds a `data-*` attribute, prepending `data-` to the given key.
+///
+/// In debug builds, this panics if `key` is empty or contains anything other than lowercase
+/// ASCII letters, digits, and `-`, which are the characters that can be used in a `data-*`
+/// attribute and still map to a property of the element's `dataset`.
+#[track_caller]
+pub fn data_attribute<V>(
+    key: &'static str,
+    value: V,
+) -> CustomAttr<Cow<'static, str>, V>
+where
+    V: AttributeValue,
+{
+    debug_assert!(
+        is_valid_data_key(key),
+        "invalid data attribute key {key:?}: expected only lowercase ASCII \
+         letters, digits, and `-`"
+    );
+    custom_attribute(Cow::Owned(format!("data-{key}")), value)
+}
+
+fn is_valid_data_key(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b == b'-')
+}
+
 /// A custom attribute with any key-value combination.
 #[derive(Debug)]
 pub struct CustomAttr<K, V>
@@ -66,7 +95,14 @@ where
         _style: &mut String,
         _inner_html: &mut String,
     ) {
+        let start = buf.len();
         self.value.to_html(self.key.as_ref(), buf);
+        // the key is only known at runtime, so it is only checked if it can matter
+        if ElementOptions::current().default_attributes && buf.len() > start {
+            if let Some(attr) = DefaultAttr::from_key(self.key.as_ref()) {
+                attr.set_explicitly();
+            }
+        }
     }
 
     fn hydrate<const FROM_SERVER: bool>(
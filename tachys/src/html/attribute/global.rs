@@ -1,7 +1,10 @@
 use super::Lang;
 use crate::{
     html::{
-        attribute::*,
+        attribute::{
+            custom::{data_attribute, CustomAttr},
+            *,
+        },
         class::{class, Class, IntoClass},
         element::{ElementType, HasElementType, HtmlElement},
         event::{on, on_target, EventDescriptor, On, Targeted},
@@ -12,6 +15,7 @@ use crate::{
     view::add_attr::AddAnyAttr,
 };
 use core::convert::From;
+use std::borrow::Cow;
 
 /// Adds an attribute that modifies the `class`.
 pub trait ClassAttribute<C>
@@ -23,6 +27,38 @@ where
 
     /// Adds a CSS class to an element.
     fn class(self, value: C) -> Self::Output;
+
+    /// Adds the CSS class that the function maps the given state to.
+    ///
+    /// This makes it declarative to style a component with a few discrete states:
+    /// ```
+    /// # use tachys::prelude::*;
+    /// # use tachys::html::element::span;
+    /// enum Status {
+    ///     Ok,
+    ///     Warn,
+    ///     Error,
+    /// }
+    ///
+    /// let status = Status::Warn;
+    /// let badge = span().class_for_state(status, |status| match status {
+    ///     Status::Ok => "ok",
+    ///     Status::Warn => "warn",
+    ///     Status::Error => "error",
+    /// });
+    /// # #[cfg(feature = "ssr")]
+    /// assert_eq!(badge.to_html(), r#"<span class="warn"></span>"#);
+    /// ```
+    fn class_for_state<S>(
+        self,
+        state: S,
+        to_class: impl FnOnce(S) -> C,
+    ) -> Self::Output
+    where
+        Self: Sized,
+    {
+        self.class(to_class(state))
+    }
 }
 
 impl<E, At, Ch, C> ClassAttribute<C> for HtmlElement<E, At, Ch>
@@ -347,6 +383,30 @@ where
     ) -> <Self as AddAnyAttr>::Output<Attr<Virtualkeyboardpolicy, V>> {
         self.add_any_attr(virtualkeyboardpolicy(value))
     }
+
+    /// Adds a `data-testid` attribute, which end-to-end tests can use to find the element.
+    ///
+    /// An element with an explicit test id keeps it when the render context generates them
+    /// [automatically](crate::ssr::RenderContext::auto_testid).
+    fn testid(
+        self,
+        value: V,
+    ) -> <Self as AddAnyAttr>::Output<CustomAttr<Cow<'static, str>, V>> {
+        self.add_any_attr(data_attribute("testid", value))
+    }
+
+    /// Adds a `data-*` attribute, like `data-user-id` for the key `user-id`.
+    ///
+    /// The key should only contain lowercase ASCII letters, digits, and `-`; this is checked in
+    /// debug builds. See [`data_attribute`].
+    #[track_caller]
+    fn data_attr(
+        self,
+        key: &'static str,
+        value: V,
+    ) -> <Self as AddAnyAttr>::Output<CustomAttr<Cow<'static, str>, V>> {
+        self.add_any_attr(data_attribute(key, value))
+    }
 }
 
 impl<El, At, Ch, V> GlobalAttributes<V> for HtmlElement<El, At, Ch>
@@ -531,3 +591,55 @@ where
     V: AttributeValue,
 {
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{ClassAttribute, GlobalAttributes};
+    use crate::{html::element::span, view::RenderHtml};
+
+    #[derive(Clone, Copy)]
+    enum Status {
+        Ok,
+        Warn,
+        Error,
+    }
+
+    fn badge(status: Status) -> String {
+        span()
+            .class_for_state(status, |status| match status {
+                Status::Ok => "status-ok",
+                Status::Warn => "status-warn",
+                Status::Error => "status-error",
+            })
+            .to_html()
+    }
+
+    #[test]
+    fn class_for_state_renders_mapped_class() {
+        assert_eq!(badge(Status::Ok), r#"<span class="status-ok"></span>"#);
+        assert_eq!(badge(Status::Warn), r#"<span class="status-warn"></span>"#);
+        assert_eq!(
+            badge(Status::Error),
+            r#"<span class="status-error"></span>"#
+        );
+    }
+
+    #[test]
+    fn data_attr_renders_escaped_value() {
+        let html = span()
+            .data_attr("label", r#"Tom & "Jerry""#)
+            .data_attr("user-id", "42")
+            .to_html();
+        assert_eq!(
+            html,
+            r#"<span data-label="Tom &amp; &quot;Jerry&quot;" data-user-id="42"></span>"#
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "invalid data attribute key")]
+    fn data_attr_rejects_invalid_key() {
+        _ = span().data_attr("userId", "42");
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Attr, AttributeValue};
+use super::{aria::IntoAriaFlag, Attr, AttributeValue};
 use std::fmt::Debug;
 
 /// An HTML attribute key.
@@ -95,8 +95,6 @@ attributes! {
     aria_grabbed "aria-grabbed",
     /// The `aria-haspopup` attribute indicates the availability and type of interactive popup element, such as menu or dialog, that can be triggered by an element.
     aria_haspopup "aria-haspopup",
-    /// The `aria-hidden` attribute indicates whether the element is exposed to an accessibility API.
-    aria_hidden "aria-hidden",
     /// The `aria-invalid` attribute indicates the entered value does not conform to the format expected by the application.
     aria_invalid "aria-invalid",
     /// The `aria-keyshortcuts` attribute indicates keyboard shortcuts that an author has implemented to activate or give focus to an element.
@@ -657,3 +655,24 @@ attributes! {
     /// The `xmlns` attribute specifies the XML namespace of the element.
     xmlns "xmlns",
 }
+
+/// The `aria-hidden` attribute indicates whether the element is exposed to an accessibility API.
+///
+/// Like [`AriaAttributes::aria_hidden`](super::aria::AriaAttributes::aria_hidden), this takes a
+/// `bool`, which renders `aria-hidden="true"` or leaves the attribute out, or a string, which is
+/// rendered as it is.
+#[track_caller]
+pub fn aria_hidden<V>(value: V) -> Attr<AriaHidden, V::Output>
+where
+    V: IntoAriaFlag,
+{
+    Attr(AriaHidden, value.into_aria_flag())
+}
+
+/// The `aria-hidden` attribute indicates whether the element is exposed to an accessibility API.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct AriaHidden;
+
+impl AttributeKey for AriaHidden {
+    const KEY: &'static str = "aria-hidden";
+}
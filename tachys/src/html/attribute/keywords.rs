@@ -0,0 +1,383 @@
+use super::AttributeValue;
+use crate::renderer::{types::Element, Rndr};
+use std::fmt;
+
+/// Defines an enum of the keywords an attribute accepts, which can be used as its value.
+macro_rules! keywords {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            $(
+                $(#[$variant_meta:meta])*
+                $variant:ident => $keyword:literal
+            ),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $(
+                $(#[$variant_meta])*
+                $variant,
+            )*
+        }
+
+        impl $name {
+            /// The keyword as it appears in HTML.
+            pub const fn as_str(self) -> &'static str {
+                match self {
+                    $(Self::$variant => $keyword,)*
+                }
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+
+        impl AttributeValue for $name {
+            type AsyncOutput = Self;
+            type State = (Element, Self);
+            type Cloneable = Self;
+            type CloneableOwned = Self;
+
+            fn html_len(&self) -> usize {
+                self.as_str().len()
+            }
+
+            fn to_html(self, key: &str, buf: &mut String) {
+                self.as_str().to_html(key, buf);
+            }
+
+            fn to_template(_key: &str, _buf: &mut String) {}
+
+            fn hydrate<const FROM_SERVER: bool>(
+                self,
+                key: &str,
+                el: &Element,
+            ) -> Self::State {
+                if !FROM_SERVER {
+                    Rndr::set_attribute(el, key, self.as_str());
+                }
+                (el.clone(), self)
+            }
+
+            fn build(self, el: &Element, key: &str) -> Self::State {
+                Rndr::set_attribute(el, key, self.as_str());
+                (el.clone(), self)
+            }
+
+            fn rebuild(self, key: &str, state: &mut Self::State) {
+                let (el, prev_value) = state;
+                if self != *prev_value {
+                    Rndr::set_attribute(el, key, self.as_str());
+                }
+                *prev_value = self;
+            }
+
+            fn into_cloneable(self) -> Self::Cloneable {
+                self
+            }
+
+            fn into_cloneable_owned(self) -> Self::CloneableOwned {
+                self
+            }
+
+            fn dry_resolve(&mut self) {}
+
+            async fn resolve(self) -> Self::AsyncOutput {
+                self
+            }
+        }
+    };
+}
+
+keywords! {
+    /// The kind of virtual keyboard a browser should show, for the `inputmode` attribute.
+    InputMode {
+        /// No virtual keyboard, for elements that provide their own input.
+        None => "none",
+        /// A keyboard for the user's locale.
+        Text => "text",
+        /// A keyboard for fractional numbers, including the decimal separator.
+        Decimal => "decimal",
+        /// A keyboard for whole numbers.
+        Numeric => "numeric",
+        /// A keyboard for telephone numbers, including `*` and `#`.
+        Tel => "tel",
+        /// A keyboard optimized for search input.
+        Search => "search",
+        /// A keyboard for email addresses, including `@`.
+        Email => "email",
+        /// A keyboard for URLs, including `/`.
+        Url => "url",
+    }
+}
+
+keywords! {
+    /// The label or icon a virtual keyboard should show on its enter key, for the
+    /// `enterkeyhint` attribute.
+    EnterKeyHint {
+        /// Inserts a new line.
+        Enter => "enter",
+        /// There is nothing more to input, and the input method editor will be closed.
+        Done => "done",
+        /// Takes the user to the target of the text they typed.
+        Go => "go",
+        /// Takes the user to the next field that accepts text.
+        Next => "next",
+        /// Takes the user to the previous field that accepts text.
+        Previous => "previous",
+        /// Takes the user to the results of searching for the text they typed.
+        Search => "search",
+        /// Delivers the text to its target.
+        Send => "send",
+    }
+}
+
+keywords! {
+    /// How a `<script>` is interpreted, for its `type` attribute.
+    ScriptType {
+        /// A JavaScript module.
+        Module => "module",
+        /// An import map, which controls how modules are resolved.
+        ImportMap => "importmap",
+        /// Rules for prefetching or prerendering other pages.
+        SpeculationRules => "speculationrules",
+    }
+}
+
+keywords! {
+    /// Whether a resource is fetched using CORS, and with which credentials, for the
+    /// `crossorigin` attribute.
+    CrossOrigin {
+        /// A CORS request, which only sends credentials to the same origin.
+        Anonymous => "anonymous",
+        /// A CORS request that always sends credentials.
+        UseCredentials => "use-credentials",
+    }
+}
+
+keywords! {
+    /// A [WAI-ARIA role](https://www.w3.org/TR/wai-aria-1.2/#role_definitions), for the `role`
+    /// attribute.
+    AriaRole {
+        /// An important, usually time-sensitive, message.
+        Alert => "alert",
+        /// A dialog that interrupts the user to show an alert.
+        AlertDialog => "alertdialog",
+        /// A region that handles all keyboard input itself.
+        Application => "application",
+        /// A self-contained composition, like a post or a comment.
+        Article => "article",
+        /// The site-oriented content at the top of a page.
+        Banner => "banner",
+        /// A section quoted from another source.
+        Blockquote => "blockquote",
+        /// An input that triggers an action when activated.
+        Button => "button",
+        /// The caption of a table, grid, or figure.
+        Caption => "caption",
+        /// A cell in a table.
+        Cell => "cell",
+        /// A checkable input with three possible values: `true`, `false`, or `mixed`.
+        Checkbox => "checkbox",
+        /// A section of computer code.
+        Code => "code",
+        /// A cell that contains header information for a column.
+        ColumnHeader => "columnheader",
+        /// An input that controls another element, like a listbox, that can pop up to help set its value.
+        Combobox => "combobox",
+        /// A section that supports the main content, while remaining meaningful on its own.
+        Complementary => "complementary",
+        /// Information about the page, like copyrights and privacy statements.
+        ContentInfo => "contentinfo",
+        /// The definition of a term or concept.
+        Definition => "definition",
+        /// Content that was deleted or is suggested for deletion.
+        Deletion => "deletion",
+        /// A window that is separate from the rest of the page.
+        Dialog => "dialog",
+        /// Content that is meant to be read rather than operated.
+        Document => "document",
+        /// Emphasized text.
+        Emphasis => "emphasis",
+        /// A scrollable list of articles, where new ones can be loaded as the user scrolls.
+        Feed => "feed",
+        /// A figure, like an image or a diagram, that is referenced from the main content.
+        Figure => "figure",
+        /// A landmark containing the elements of a form.
+        Form => "form",
+        /// A nameless container with no semantic meaning.
+        Generic => "generic",
+        /// A composite widget containing rows of cells that can be navigated with the keyboard.
+        Grid => "grid",
+        /// A cell in a grid or treegrid.
+        GridCell => "gridcell",
+        /// A set of related elements that is not a page landmark.
+        Group => "group",
+        /// The heading of a section.
+        Heading => "heading",
+        /// A set of elements that together form a single image.
+        Img => "img",
+        /// Content that was added or is suggested for addition.
+        Insertion => "insertion",
+        /// A reference to a resource that navigates to it when activated.
+        Link => "link",
+        /// A list of items.
+        List => "list",
+        /// A widget for choosing one or more items from a list.
+        Listbox => "listbox",
+        /// An item in a list.
+        ListItem => "listitem",
+        /// A live region where new information is added in a meaningful order.
+        Log => "log",
+        /// The main content of the page.
+        Main => "main",
+        /// A live region with non-essential information that changes frequently.
+        Marquee => "marquee",
+        /// A mathematical expression.
+        Math => "math",
+        /// A widget that offers a list of choices.
+        Menu => "menu",
+        /// A menu that is usually always visible and laid out horizontally.
+        Menubar => "menubar",
+        /// An option in a menu or menubar.
+        MenuItem => "menuitem",
+        /// A checkable option in a menu.
+        MenuItemCheckbox => "menuitemcheckbox",
+        /// A checkable option in a group of options in a menu, only one of which can be checked.
+        MenuItemRadio => "menuitemradio",
+        /// A scalar value within a known range.
+        Meter => "meter",
+        /// A set of links for navigating the page or related pages.
+        Navigation => "navigation",
+        /// An element whose implicit role should not be exposed, the same as `presentation`.
+        None => "none",
+        /// A section whose content is parenthetic to the main content.
+        Note => "note",
+        /// A selectable item in a listbox.
+        Option => "option",
+        /// A paragraph of text.
+        Paragraph => "paragraph",
+        /// An element whose implicit role should not be exposed.
+        Presentation => "presentation",
+        /// The progress of a task that takes a long time.
+        ProgressBar => "progressbar",
+        /// A checkable input in a group of inputs, only one of which can be checked.
+        Radio => "radio",
+        /// A group of radio buttons.
+        RadioGroup => "radiogroup",
+        /// A section that is important enough to be listed as a landmark.
+        Region => "region",
+        /// A row of cells in a table, grid, or treegrid.
+        Row => "row",
+        /// A group of rows in a table, grid, or treegrid.
+        RowGroup => "rowgroup",
+        /// A cell that contains header information for a row.
+        RowHeader => "rowheader",
+        /// A control for scrolling the content of a viewing area.
+        Scrollbar => "scrollbar",
+        /// A landmark for the search functionality of the page.
+        Search => "search",
+        /// A text input for search queries.
+        Searchbox => "searchbox",
+        /// A divider between sections of content or groups of menu items.
+        Separator => "separator",
+        /// An input for choosing a value from a range.
+        Slider => "slider",
+        /// An input for choosing from a range of discrete values, with buttons to step through them.
+        SpinButton => "spinbutton",
+        /// A live region with advisory information that isn't important enough to be an alert.
+        Status => "status",
+        /// Important text.
+        Strong => "strong",
+        /// Subscript text.
+        Subscript => "subscript",
+        /// Superscript text.
+        Superscript => "superscript",
+        /// A checkbox that represents on and off rather than checked and unchecked.
+        Switch => "switch",
+        /// A tab in a tablist, which shows its tabpanel when activated.
+        Tab => "tab",
+        /// A static table of data.
+        Table => "table",
+        /// A list of tabs.
+        Tablist => "tablist",
+        /// The content shown for a tab.
+        TabPanel => "tabpanel",
+        /// A word or phrase that is defined elsewhere.
+        Term => "term",
+        /// An input for free-form text.
+        Textbox => "textbox",
+        /// A specific point in time.
+        Time => "time",
+        /// A live region with a counter that measures elapsed or remaining time.
+        Timer => "timer",
+        /// A set of commonly used controls, like buttons.
+        Toolbar => "toolbar",
+        /// A popup that describes an element.
+        Tooltip => "tooltip",
+        /// A hierarchical list whose items can be expanded and collapsed.
+        Tree => "tree",
+        /// A grid whose rows can be expanded and collapsed like a tree.
+        TreeGrid => "treegrid",
+        /// An item in a tree.
+        TreeItem => "treeitem",
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{CrossOrigin, EnterKeyHint, InputMode, ScriptType};
+    use crate::{
+        html::{
+            attribute::global::GlobalAttributes,
+            element::{input, script, ElementChild},
+        },
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn keywords_render_as_lowercase_tokens() {
+        let html = input()
+            .enterkeyhint(EnterKeyHint::Search)
+            .inputmode(InputMode::Numeric)
+            .to_html();
+        assert_eq!(
+            html,
+            r#"<input enterkeyhint="search" inputmode="numeric">"#
+        );
+    }
+
+    #[test]
+    fn module_script_with_integrity() {
+        let html = script()
+            .crossorigin(CrossOrigin::Anonymous)
+            .integrity("sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC")
+            .r#type(ScriptType::Module)
+            .child("if (a < b && c) { run(); }")
+            .to_html();
+        assert_eq!(
+            html,
+            "<script crossorigin=\"anonymous\" \
+             integrity=\"sha384-oqVuAfXRKap7fdgcCY5uykM6+R9GqQ8K/\
+             uxy9rx7HNQlGYl1kPzQho1wx4JwY8wC\" \
+             type=\"module\">if (a < b && c) { run(); }</script>"
+        );
+    }
+
+    #[test]
+    fn script_attributes_are_escaped_but_body_is_not() {
+        let html = script()
+            .integrity("sha384-\" onload=\"x")
+            .child("let s = \"<b>\";")
+            .to_html();
+        assert_eq!(
+            html,
+            r#"<script integrity="sha384-&quot; onload=&quot;x">let s = "<b>";</script>"#
+        );
+    }
+}
@@ -7,10 +7,17 @@ pub mod custom;
 /// Traits to define global attribute methods on all HTML elements.
 pub mod global;
 mod key;
+/// Typed values for attributes that accept a fixed set of keywords.
+pub mod keywords;
 pub(crate) mod maybe_next_attr_erasure_macros;
+/// Attributes in XML namespaces, like `xlink:href`.
+pub mod namespaced;
 mod value;
 
-use crate::view::{Position, ToTemplate};
+use crate::{
+    ssr::{collecting_assets, is_asset_attribute, record_asset, DefaultAttr},
+    view::{Position, ToTemplate},
+};
 pub use key::*;
 use maybe_next_attr_erasure_macros::{
     next_attr_combine, next_attr_output_type,
@@ -239,7 +246,17 @@ where
         _style: &mut String,
         _inner_html: &mut String,
     ) {
+        let start = buf.len();
         self.1.to_html(K::KEY, buf);
+        // only a few keys matter to these, which is known when this is compiled
+        if const { is_asset_attribute(K::KEY) } && collecting_assets() {
+            record_asset(K::KEY, &buf[start..]);
+        }
+        if let Some(attr) = const { DefaultAttr::from_key(K::KEY) } {
+            if buf.len() > start {
+                attr.set_explicitly();
+            }
+        }
     }
 
     fn hydrate<const FROM_SERVER: bool>(
@@ -0,0 +1,180 @@
+use super::{
+    escape_attr,
+    maybe_next_attr_erasure_macros::{
+        next_attr_combine, next_attr_output_type,
+    },
+    Attribute, NamedAttributeKey, NextAttribute,
+};
+use crate::{
+    renderer::{types::Element, Rndr},
+    view::{Position, ToTemplate},
+};
+use std::borrow::Cow;
+
+/// The namespace of the `xlink:` attributes.
+pub const XLINK_NAMESPACE: &str = "http://www.w3.org/1999/xlink";
+/// The namespace of the `xml:` attributes.
+pub const XML_NAMESPACE: &str = "http://www.w3.org/XML/1998/namespace";
+
+/// Creates an attribute in the given namespace, like `xlink:href` or `xml:lang`.
+///
+/// `name` is the qualified name, including the prefix. It is rendered as-is to HTML, and created
+/// with [`setAttributeNS`](https://developer.mozilla.org/en-US/docs/Web/API/Element/setAttributeNS)
+/// in the DOM, so that the browser associates it with `namespace`.
+pub fn namespaced_attribute(
+    namespace: &'static str,
+    name: &'static str,
+    value: impl Into<Cow<'static, str>>,
+) -> NamespacedAttr {
+    NamespacedAttr {
+        namespace,
+        name,
+        value: value.into(),
+    }
+}
+
+/// Creates an `xlink:href` attribute, as used by older SVG content.
+///
+/// ```
+/// # use tachys::prelude::*;
+/// # use tachys::{html::attribute::namespaced::xlink_href, svg::r#use};
+/// let icon = r#use().add_any_attr(xlink_href("#icon"));
+/// # #[cfg(feature = "ssr")]
+/// assert_eq!(icon.to_html(), r##"<use xlink:href="#icon"></use>"##);
+/// ```
+pub fn xlink_href(value: impl Into<Cow<'static, str>>) -> NamespacedAttr {
+    namespaced_attribute(XLINK_NAMESPACE, "xlink:href", value)
+}
+
+/// Creates an `xml:lang` attribute.
+pub fn xml_lang(value: impl Into<Cow<'static, str>>) -> NamespacedAttr {
+    namespaced_attribute(XML_NAMESPACE, "xml:lang", value)
+}
+
+/// An attribute that belongs to an XML namespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespacedAttr {
+    namespace: &'static str,
+    name: &'static str,
+    value: Cow<'static, str>,
+}
+
+impl NamespacedAttr {
+    fn set(&self, el: &Element) {
+        Rndr::set_attribute_ns(el, self.namespace, self.name, &self.value);
+    }
+}
+
+impl Attribute for NamespacedAttr {
+    const MIN_LENGTH: usize = 0;
+
+    type AsyncOutput = Self;
+    type State = (Element, Self);
+    type Cloneable = Self;
+    type CloneableOwned = Self;
+
+    fn html_len(&self) -> usize {
+        self.name.len() + 4 + self.value.len()
+    }
+
+    fn to_html(
+        self,
+        buf: &mut String,
+        _class: &mut String,
+        _style: &mut String,
+        _inner_html: &mut String,
+    ) {
+        buf.push(' ');
+        buf.push_str(self.name);
+        buf.push_str("=\"");
+        buf.push_str(&escape_attr(&self.value));
+        buf.push('"');
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(self, el: &Element) -> Self::State {
+        if !FROM_SERVER {
+            self.set(el);
+        }
+        (el.clone(), self)
+    }
+
+    fn build(self, el: &Element) -> Self::State {
+        self.set(el);
+        (el.clone(), self)
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let (el, prev) = state;
+        if self.name != prev.name {
+            Rndr::remove_attribute(el, prev.name);
+        }
+        if self != *prev {
+            self.set(el);
+        }
+        *prev = self;
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn keys(&self) -> Vec<NamedAttributeKey> {
+        vec![NamedAttributeKey::Attribute(self.name.into())]
+    }
+}
+
+impl NextAttribute for NamespacedAttr {
+    next_attr_output_type!(Self, NewAttr);
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        new_attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        next_attr_combine!(self, new_attr)
+    }
+}
+
+impl ToTemplate for NamespacedAttr {
+    fn to_template(
+        _buf: &mut String,
+        _class: &mut String,
+        _style: &mut String,
+        _inner_html: &mut String,
+        _position: &mut Position,
+    ) {
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{xlink_href, xml_lang};
+    use crate::{
+        html::element::p,
+        svg::r#use,
+        view::{add_attr::AddAnyAttr, RenderHtml},
+    };
+
+    #[test]
+    fn renders_prefixed_name() {
+        let html = r#use()
+            .add_any_attr((xlink_href("#icon"), xml_lang("en")))
+            .to_html();
+        assert_eq!(html, r##"<use xlink:href="#icon" xml:lang="en"></use>"##);
+    }
+
+    #[test]
+    fn escapes_value() {
+        let html = p().add_any_attr(xml_lang("\"en\"")).to_html();
+        assert_eq!(html, r#"<p xml:lang="&quot;en&quot;"></p>"#);
+    }
+}
@@ -506,6 +506,114 @@ impl AttributeValue for Arc<str> {
         self
     }
 }
+/// Joins a list of tokens (like the value of `part`, `rel`, or `class`) with spaces, or returns
+/// `None` for an empty list, so that the attribute is left out.
+fn join_tokens(tokens: &[&str]) -> Option<String> {
+    (!tokens.is_empty()).then(|| tokens.join(" "))
+}
+
+impl<'a> AttributeValue for &'a [&'a str] {
+    type AsyncOutput = Self;
+    type State = <Option<String> as AttributeValue>::State;
+    type Cloneable = Self;
+    type CloneableOwned = Option<Arc<str>>;
+
+    fn html_len(&self) -> usize {
+        self.iter().map(|token| token.len() + 1).sum()
+    }
+
+    fn to_html(self, key: &str, buf: &mut String) {
+        join_tokens(self).to_html(key, buf);
+    }
+
+    fn to_template(_key: &str, _buf: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        key: &str,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        join_tokens(self).hydrate::<FROM_SERVER>(key, el)
+    }
+
+    fn build(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        join_tokens(self).build(el, key)
+    }
+
+    fn rebuild(self, key: &str, state: &mut Self::State) {
+        join_tokens(self).rebuild(key, state);
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        join_tokens(self).map(Arc::from)
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+}
+
+impl<'a, const N: usize> AttributeValue for &'a [&'a str; N] {
+    type AsyncOutput = Self;
+    type State = <Option<String> as AttributeValue>::State;
+    type Cloneable = Self;
+    type CloneableOwned = Option<Arc<str>>;
+
+    fn html_len(&self) -> usize {
+        self.as_slice().html_len()
+    }
+
+    fn to_html(self, key: &str, buf: &mut String) {
+        self.as_slice().to_html(key, buf);
+    }
+
+    fn to_template(_key: &str, _buf: &mut String) {}
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        key: &str,
+        el: &crate::renderer::types::Element,
+    ) -> Self::State {
+        self.as_slice().hydrate::<FROM_SERVER>(key, el)
+    }
+
+    fn build(
+        self,
+        el: &crate::renderer::types::Element,
+        key: &str,
+    ) -> Self::State {
+        self.as_slice().build(el, key)
+    }
+
+    fn rebuild(self, key: &str, state: &mut Self::State) {
+        self.as_slice().rebuild(key, state);
+    }
+
+    fn into_cloneable(self) -> Self::Cloneable {
+        self
+    }
+
+    fn into_cloneable_owned(self) -> Self::CloneableOwned {
+        self.as_slice().into_cloneable_owned()
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+}
+
 // TODO impl AttributeValue for Rc<str> and Arc<str> too
 
 impl AttributeValue for bool {
@@ -534,7 +642,8 @@ impl AttributeValue for bool {
     ) -> Self::State {
         // if we're actually hydrating from SSRed HTML, we don't need to set the attribute
         // if we're hydrating from a CSR-cloned <template>, we do need to set non-StaticAttr attributes
-        if !FROM_SERVER {
+        // a `false` boolean attribute is left out of the HTML, so there is nothing to set
+        if !FROM_SERVER && self {
             Rndr::set_attribute(el, key, "");
         }
         (el.clone(), self)
@@ -766,3 +875,45 @@ render_primitive![
     NonZeroIsize,
     NonZeroUsize,
 ];
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use crate::{
+        html::{
+            attribute::global::GlobalAttributes,
+            element::{button, span},
+        },
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn boolean_attribute_renders_by_presence() {
+        assert_eq!(
+            button().disabled(true).to_html(),
+            "<button disabled></button>"
+        );
+        assert_eq!(button().disabled(false).to_html(), "<button></button>");
+        assert_eq!(
+            span().hidden(false).id("a").to_html(),
+            r#"<span id="a"></span>"#
+        );
+    }
+
+    #[test]
+    fn token_list_joins_with_spaces() {
+        let html = span().part(&["label", "active"]).to_html();
+        assert_eq!(html, r#"<span part="label active"></span>"#);
+
+        let parts: &[&str] = &["label"];
+        assert_eq!(
+            span().part(parts).to_html(),
+            r#"<span part="label"></span>"#
+        );
+    }
+
+    #[test]
+    fn empty_token_list_omits_attribute() {
+        let parts: &[&str] = &[];
+        assert_eq!(span().part(parts).to_html(), "<span></span>");
+    }
+}
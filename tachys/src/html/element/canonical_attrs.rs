@@ -0,0 +1,92 @@
+//! Sorts rendered attributes by name, so that the HTML for an element doesn't depend on the
+//! order its attributes were declared in. This is useful for snapshot tests.
+
+/// Sorts the attributes rendered into the buffer from `start` by name.
+pub(crate) fn sort_from(buf: &mut String, start: usize) {
+    let attrs = buf.split_off(start);
+    push_sorted(&attrs, buf);
+}
+
+/// Pushes the attributes rendered in `attrs` into the buffer, sorted by name.
+///
+/// Attributes with the same name keep their relative order. `attrs` is expected to be a series
+/// of ` name` or ` name="value"`; anything after a part that can't be read this way is pushed
+/// as-is.
+pub(crate) fn push_sorted(attrs: &str, buf: &mut String) {
+    let mut parsed = Vec::new();
+    let mut rest = attrs;
+    while let Some(attr) = rest.strip_prefix(' ') {
+        let name_len = attr.find(['=', ' ']).unwrap_or(attr.len());
+        let len = match attr[name_len..].strip_prefix("=\"") {
+            // a `"` inside an attribute value is always escaped, so the next one closes it
+            Some(value) => match value.find('"') {
+                Some(value_len) => name_len + 2 + value_len + 1,
+                None => break,
+            },
+            None => name_len,
+        };
+        parsed.push((&attr[..name_len], &attr[..len]));
+        rest = &attr[len..];
+    }
+
+    parsed.sort_by_key(|(name, _)| *name);
+    for (_, attr) in parsed {
+        buf.push(' ');
+        buf.push_str(attr);
+    }
+    buf.push_str(rest);
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::push_sorted;
+    use crate::{
+        html::{
+            attribute::global::GlobalAttributes,
+            element::{img, input},
+        },
+        ssr::RenderContext,
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn sorts_by_name() {
+        let mut buf = String::new();
+        push_sorted(
+            r#" type="text" disabled id="a b" data-x="&quot;""#,
+            &mut buf,
+        );
+        assert_eq!(buf, r#" data-x="&quot;" disabled id="a b" type="text""#);
+    }
+
+    #[test]
+    fn declaration_order_does_not_change_html() {
+        let a = input()
+            .id("name")
+            .r#type("text")
+            .title("Name")
+            .disabled(true);
+        let b = input()
+            .disabled(true)
+            .title("Name")
+            .id("name")
+            .r#type("text");
+        let html = a.to_html();
+        assert_eq!(html, b.to_html());
+        assert_eq!(
+            html,
+            r#"<input disabled id="name" title="Name" type="text">"#
+        );
+    }
+
+    #[test]
+    fn default_attributes_are_sorted_too() {
+        let html = RenderContext::new().default_lazy_loading(true).run(|| {
+            (img().src("a.png"), img().src("b.png").loading("eager")).to_html()
+        });
+        assert_eq!(
+            html,
+            r#"<img loading="lazy" src="a.png"><img loading="eager" src="b.png">"#
+        );
+    }
+}
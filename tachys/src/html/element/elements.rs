@@ -80,6 +80,8 @@ macro_rules! html_element_inner {
                 const SELF_CLOSING: bool = false;
                 const ESCAPE_CHILDREN: bool = $escape;
                 const NAMESPACE: Option<&'static str> = None;
+                const NEEDS_NONCE: bool =
+                    matches!(stringify!($tag).as_bytes(), b"script" | b"style");
 
                 #[inline(always)]
                 fn tag(&self) -> &str {
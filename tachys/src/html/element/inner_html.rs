@@ -7,9 +7,15 @@ use crate::{
         Attribute, NamedAttributeKey, NextAttribute,
     },
     renderer::Rndr,
+    ssr::RenderContext,
     view::add_attr::AddAnyAttr,
 };
-use std::{future::Future, sync::Arc};
+use std::{
+    borrow::Cow,
+    fmt::{self, Debug},
+    future::Future,
+    sync::Arc,
+};
 
 /// Returns an [`Attribute`] that sets the inner HTML of an element.
 ///
@@ -18,7 +24,8 @@ use std::{future::Future, sync::Arc};
 /// # Security
 /// Be very careful when using this method. Always remember to
 /// sanitize the input to avoid a cross-site scripting (XSS)
-/// vulnerability.
+/// vulnerability, for example with a
+/// [`RenderContext::inner_html_sanitizer`].
 #[inline(always)]
 pub fn inner_html<T>(value: T) -> InnerHtml<T>
 where
@@ -163,6 +170,52 @@ where
     }
 }
 
+/// A function that is run over the HTML given to [`inner_html`] before it is rendered, like an
+/// HTML sanitizer that strips `<script>` elements and event handler attributes.
+///
+/// A sanitizer is set for a render with
+/// [`RenderContext::inner_html_sanitizer`](crate::ssr::RenderContext::inner_html_sanitizer).
+#[derive(Clone, Copy)]
+pub struct Sanitizer(pub fn(&str) -> String);
+
+impl Debug for Sanitizer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Sanitizer(..)")
+    }
+}
+
+impl PartialEq for Sanitizer {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::fn_addr_eq(self.0, other.0)
+    }
+}
+
+impl Eq for Sanitizer {}
+
+pub(crate) fn current_sanitizer() -> Option<Sanitizer> {
+    RenderContext::with_current(|cx| cx.inner_html_sanitizer)
+}
+
+pub(crate) fn sanitize(
+    sanitizer: Option<Sanitizer>,
+    html: &str,
+) -> Cow<'_, str> {
+    match sanitizer {
+        Some(Sanitizer(sanitize)) => Cow::Owned(sanitize(html)),
+        None => Cow::Borrowed(html),
+    }
+}
+
+/// Sets the inner HTML of an element on the client, sanitizing it with the sanitizer that was
+/// current when the element was built, so that later updates are sanitized the same way.
+pub(crate) fn set_sanitized_inner_html(
+    el: &crate::renderer::types::Element,
+    html: &str,
+    sanitizer: Option<Sanitizer>,
+) {
+    Rndr::set_inner_html(el, &sanitize(sanitizer, html));
+}
+
 /// A possible value for [`InnerHtml`].
 pub trait InnerHtmlValue: Send {
     /// The type after all async data have resolved.
@@ -213,7 +266,7 @@ pub trait InnerHtmlValue: Send {
 
 impl InnerHtmlValue for String {
     type AsyncOutput = Self;
-    type State = (crate::renderer::types::Element, Self);
+    type State = (crate::renderer::types::Element, Self, Option<Sanitizer>);
     type Cloneable = Arc<str>;
     type CloneableOwned = Arc<str>;
 
@@ -222,7 +275,7 @@ impl InnerHtmlValue for String {
     }
 
     fn to_html(self, buf: &mut String) {
-        buf.push_str(&self);
+        buf.push_str(&sanitize(current_sanitizer(), &self));
     }
 
     fn to_template(_buf: &mut String) {}
@@ -231,20 +284,22 @@ impl InnerHtmlValue for String {
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
+        let sanitizer = current_sanitizer();
         if !FROM_SERVER {
-            Rndr::set_inner_html(el, &self);
+            set_sanitized_inner_html(el, &self, sanitizer);
         }
-        (el.clone(), self)
+        (el.clone(), self, sanitizer)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_inner_html(el, &self);
-        (el.clone(), self)
+        let sanitizer = current_sanitizer();
+        set_sanitized_inner_html(el, &self, sanitizer);
+        (el.clone(), self, sanitizer)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         if self != state.1 {
-            Rndr::set_inner_html(&state.0, &self);
+            set_sanitized_inner_html(&state.0, &self, state.2);
             state.1 = self;
         }
     }
@@ -266,7 +321,7 @@ impl InnerHtmlValue for String {
 
 impl InnerHtmlValue for Arc<str> {
     type AsyncOutput = Self;
-    type State = (crate::renderer::types::Element, Self);
+    type State = (crate::renderer::types::Element, Self, Option<Sanitizer>);
     type Cloneable = Self;
     type CloneableOwned = Self;
 
@@ -275,7 +330,7 @@ impl InnerHtmlValue for Arc<str> {
     }
 
     fn to_html(self, buf: &mut String) {
-        buf.push_str(&self);
+        buf.push_str(&sanitize(current_sanitizer(), &self));
     }
 
     fn to_template(_buf: &mut String) {}
@@ -284,20 +339,22 @@ impl InnerHtmlValue for Arc<str> {
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
+        let sanitizer = current_sanitizer();
         if !FROM_SERVER {
-            Rndr::set_inner_html(el, &self);
+            set_sanitized_inner_html(el, &self, sanitizer);
         }
-        (el.clone(), self)
+        (el.clone(), self, sanitizer)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_inner_html(el, &self);
-        (el.clone(), self)
+        let sanitizer = current_sanitizer();
+        set_sanitized_inner_html(el, &self, sanitizer);
+        (el.clone(), self, sanitizer)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         if self != state.1 {
-            Rndr::set_inner_html(&state.0, &self);
+            set_sanitized_inner_html(&state.0, &self, state.2);
             state.1 = self;
         }
     }
@@ -319,7 +376,7 @@ impl InnerHtmlValue for Arc<str> {
 
 impl InnerHtmlValue for &str {
     type AsyncOutput = Self;
-    type State = (crate::renderer::types::Element, Self);
+    type State = (crate::renderer::types::Element, Self, Option<Sanitizer>);
     type Cloneable = Self;
     type CloneableOwned = Arc<str>;
 
@@ -328,7 +385,7 @@ impl InnerHtmlValue for &str {
     }
 
     fn to_html(self, buf: &mut String) {
-        buf.push_str(self);
+        buf.push_str(&sanitize(current_sanitizer(), self));
     }
 
     fn to_template(_buf: &mut String) {}
@@ -337,20 +394,22 @@ impl InnerHtmlValue for &str {
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
+        let sanitizer = current_sanitizer();
         if !FROM_SERVER {
-            Rndr::set_inner_html(el, self);
+            set_sanitized_inner_html(el, self, sanitizer);
         }
-        (el.clone(), self)
+        (el.clone(), self, sanitizer)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_inner_html(el, self);
-        (el.clone(), self)
+        let sanitizer = current_sanitizer();
+        set_sanitized_inner_html(el, self, sanitizer);
+        (el.clone(), self, sanitizer)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         if self != state.1 {
-            Rndr::set_inner_html(&state.0, self);
+            set_sanitized_inner_html(&state.0, self, state.2);
             state.1 = self;
         }
     }
@@ -437,3 +496,47 @@ where
         self
     }
 }
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::InnerHtmlAttribute;
+    use crate::{html::element::div, ssr::RenderContext, view::RenderHtml};
+    use futures::{executor::block_on, StreamExt};
+
+    const HTML: &str = "<b>hi</b><script>alert(1)</script>!";
+
+    fn strip_scripts(html: &str) -> String {
+        let mut sanitized = String::new();
+        let mut rest = html;
+        while let Some(start) = rest.find("<script") {
+            sanitized.push_str(&rest[..start]);
+            rest = match rest[start..].find("</script>") {
+                Some(end) => &rest[start + end + "</script>".len()..],
+                None => "",
+            };
+        }
+        sanitized.push_str(rest);
+        sanitized
+    }
+
+    #[test]
+    fn inner_html_is_verbatim_by_default() {
+        let html = div().inner_html(HTML).to_html();
+        assert_eq!(html, format!("<div>{HTML}</div>"));
+    }
+
+    #[test]
+    fn sanitizer_runs_over_inner_html() {
+        let cx = RenderContext::new().inner_html_sanitizer(strip_scripts);
+        let html = cx.clone().run(|| div().inner_html(HTML).to_html());
+        assert_eq!(html, "<div><b>hi</b>!</div>");
+
+        let stream = cx.run(|| {
+            div().inner_html(HTML.to_string()).to_html_stream_in_order()
+        });
+        assert_eq!(
+            block_on(stream.collect::<String>()),
+            "<div><b>hi</b>!</div>"
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use super::{ElementState, ElementType, HtmlElement};
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute, NextAttribute},
+    hydration::Cursor,
+    renderer::{types::Placeholder, CastFrom, Rndr},
+    ssr::StreamBuilder,
+    view::{
+        add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+        RenderHtml,
+    },
+};
+use either_of::Either;
+
+/// An element that may or may not be present.
+///
+/// Unlike an `Option<HtmlElement<_, _, _>>`, which renders a `<!>` marker in place of a missing
+/// element, this renders nothing at all when the element is missing. While it is missing in the
+/// DOM, an empty comment is kept in its place, so that the element can be inserted there when it
+/// is added. That comment is created during hydration, rather than being sent from the server.
+#[derive(Debug, Clone)]
+pub struct MaybeElement<E, At, Ch>(Option<HtmlElement<E, At, Ch>>);
+
+impl<E, At, Ch> MaybeElement<E, At, Ch> {
+    /// Creates an element slot that renders the element if it is `Some`.
+    pub fn new(element: Option<HtmlElement<E, At, Ch>>) -> Self {
+        Self(element)
+    }
+
+    /// Returns the element, if it is present.
+    pub fn into_inner(self) -> Option<HtmlElement<E, At, Ch>> {
+        self.0
+    }
+}
+
+impl<E, At, Ch> From<Option<HtmlElement<E, At, Ch>>>
+    for MaybeElement<E, At, Ch>
+{
+    fn from(element: Option<HtmlElement<E, At, Ch>>) -> Self {
+        Self(element)
+    }
+}
+
+/// Retained view state for a [`MaybeElement`]: either the element, or the comment that stands in
+/// for it.
+pub type MaybeElementState<At, Ch> = Either<
+    ElementState<<At as Attribute>::State, <Ch as Render>::State>,
+    Placeholder,
+>;
+
+impl<E, At, Ch> Render for MaybeElement<E, At, Ch>
+where
+    E: ElementType,
+    At: Attribute,
+    Ch: Render,
+{
+    type State = MaybeElementState<At, Ch>;
+
+    fn build(self) -> Self::State {
+        match self.0 {
+            Some(element) => Either::Left(element.build()),
+            None => Either::Right(Rndr::create_placeholder()),
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let new_state = match (self.0, &mut *state) {
+            (Some(element), Either::Left(prev)) => {
+                element.rebuild(prev);
+                return;
+            }
+            (None, Either::Right(_)) => return,
+            (Some(element), Either::Right(placeholder)) => {
+                let mut new = element.build();
+                placeholder.insert_before_this(&mut new);
+                placeholder.unmount();
+                Either::Left(new)
+            }
+            (None, Either::Left(prev)) => {
+                let mut placeholder = Rndr::create_placeholder();
+                prev.insert_before_this(&mut placeholder);
+                prev.unmount();
+                Either::Right(placeholder)
+            }
+        };
+        *state = new_state;
+    }
+}
+
+impl<E, At, Ch> AddAnyAttr for MaybeElement<E, At, Ch>
+where
+    E: ElementType + Send,
+    At: Attribute + Send,
+    Ch: RenderHtml + Send,
+{
+    type Output<SomeNewAttr: Attribute> =
+        MaybeElement<E, <At as NextAttribute>::Output<SomeNewAttr>, Ch>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        MaybeElement(self.0.map(|element| element.add_any_attr(attr)))
+    }
+}
+
+impl<E, At, Ch> RenderHtml for MaybeElement<E, At, Ch>
+where
+    E: ElementType + Send,
+    At: Attribute + Send,
+    Ch: RenderHtml + Send,
+{
+    type AsyncOutput = MaybeElement<E, At::AsyncOutput, Ch::AsyncOutput>;
+    type Owned = MaybeElement<E, At::CloneableOwned, Ch::Owned>;
+
+    const MIN_LENGTH: usize = 0;
+
+    fn dry_resolve(&mut self) {
+        if let Some(element) = self.0.as_mut() {
+            element.dry_resolve();
+        }
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        match self.0 {
+            Some(element) => MaybeElement(Some(element.resolve().await)),
+            None => MaybeElement(None),
+        }
+    }
+
+    fn html_len(&self) -> usize {
+        self.0.as_ref().map(RenderHtml::html_len).unwrap_or(0)
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) {
+        if let Some(element) = self.0 {
+            element.to_html_with_buf(
+                buf,
+                position,
+                escape,
+                mark_branches,
+                extra_attrs,
+            );
+        }
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) where
+        Self: Sized,
+    {
+        if let Some(element) = self.0 {
+            element.to_html_async_with_buf::<OUT_OF_ORDER>(
+                buf,
+                position,
+                escape,
+                mark_branches,
+                extra_attrs,
+            );
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        match self.0 {
+            Some(element) => {
+                Either::Left(element.hydrate::<FROM_SERVER>(cursor, position))
+            }
+            None => Either::Right(insert_placeholder(cursor, position)),
+        }
+    }
+
+    async fn hydrate_async(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        match self.0 {
+            Some(element) => {
+                Either::Left(element.hydrate_async(cursor, position).await)
+            }
+            None => Either::Right(insert_placeholder(cursor, position)),
+        }
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        MaybeElement(self.0.map(RenderHtml::into_owned))
+    }
+}
+
+/// Inserts a placeholder where the missing element would have been hydrated, because the server
+/// did not render one, and moves the cursor onto it.
+fn insert_placeholder(
+    cursor: &Cursor,
+    position: &PositionState,
+) -> Placeholder {
+    let placeholder = Rndr::create_placeholder();
+    let current = cursor.current();
+    let (parent, anchor) = match position.get() {
+        Position::FirstChild => {
+            let anchor = Rndr::first_child(&current);
+            (Some(current), anchor)
+        }
+        Position::Current => (Rndr::get_parent(&current), Some(current)),
+        _ => (Rndr::get_parent(&current), Rndr::next_sibling(&current)),
+    };
+    if let Some(parent) =
+        parent.and_then(crate::renderer::types::Element::cast_from)
+    {
+        Rndr::insert_node(&parent, placeholder.as_ref(), anchor.as_ref());
+    }
+    cursor.set(placeholder.clone().into());
+    position.set(Position::NextChild);
+    placeholder
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::MaybeElement;
+    use crate::{
+        html::element::{li, ul, ElementChild, Li},
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn missing_element_renders_nothing() {
+        let present = MaybeElement::new(Some(li().child("a")));
+        assert_eq!(present.html_len(), "<li>a</li>".len());
+        assert_eq!(ul().child(present).to_html(), "<ul><li>a</li></ul>");
+
+        let missing: MaybeElement<Li, (), ()> = None.into();
+        assert_eq!(missing.html_len(), 0);
+        assert_eq!(ul().child(missing).to_html(), "<ul></ul>");
+    }
+}
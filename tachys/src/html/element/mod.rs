@@ -6,7 +6,10 @@ use crate::{
     html::attribute::Attribute,
     hydration::{failed_to_cast_element, Cursor},
     renderer::{CastFrom, Rndr},
-    ssr::StreamBuilder,
+    ssr::{
+        next_auto_testid, recycle_buffer, take_buffer, DefaultAttr,
+        ElementOptions, ExplicitAttrs, RenderContext, StreamBuilder,
+    },
     view::{
         add_attr::AddAnyAttr, IntoRender, Mountable, Position, PositionState,
         Render, RenderHtml, ToTemplate,
@@ -16,12 +19,17 @@ use const_str_slice_concat::{
     const_concat, const_concat_with_prefix, str_from_buffer,
 };
 use futures::future::join;
-use std::ops::Deref;
+use std::{fmt::Write, ops::Deref};
 
+#[cfg(feature = "canonical_attributes")]
+mod canonical_attrs;
 mod custom;
 mod element_ext;
 mod elements;
 mod inner_html;
+mod maybe;
+mod optional_tags;
+mod pretty;
 use super::attribute::{
     any_attribute::AnyAttribute, escape_attr, NextAttribute,
 };
@@ -29,6 +37,7 @@ pub use custom::*;
 pub use element_ext::*;
 pub use elements::*;
 pub use inner_html::*;
+pub use maybe::*;
 #[cfg(any(debug_assertions, leptos_debuginfo))]
 use std::panic::Location;
 
@@ -56,6 +65,106 @@ impl<E: Clone, At: Clone, Ch: Clone> Clone for HtmlElement<E, At, Ch> {
 
 impl<E: Copy, At: Copy, Ch: Copy> Copy for HtmlElement<E, At, Ch> {}
 
+impl<E, At, Ch> HtmlElement<E, At, Ch> {
+    /// Replaces the element's children with the result of applying the function to them,
+    /// keeping its tag and attributes.
+    ///
+    /// This makes it possible to wrap or rearrange the children of an element that was built
+    /// elsewhere, before it is rendered.
+    ///
+    /// ```
+    /// # use tachys::prelude::*;
+    /// # use tachys::html::element::{li, ul};
+    /// let list = ul()
+    ///     .child("item")
+    ///     .map_children(|(item,)| (li().child(item),));
+    /// # #[cfg(feature = "ssr")]
+    /// assert_eq!(list.to_html(), "<ul><li>item</li></ul>");
+    /// ```
+    pub fn map_children<NewCh>(
+        self,
+        fun: impl FnOnce(Ch) -> NewCh,
+    ) -> HtmlElement<E, At, NewCh>
+    where
+        NewCh: Render,
+    {
+        HtmlElement {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: self.defined_at,
+            tag: self.tag,
+            attributes: self.attributes,
+            children: fun(self.children),
+        }
+    }
+}
+
+impl<E: ElementType> HtmlElement<E, (), ()> {
+    /// Creates an element of a type defined outside of this crate, with no attributes or
+    /// children.
+    ///
+    /// This is how an element that overrides one of the [`ElementType`] settings, like
+    /// [`CLASS_CAPACITY_HINT`](ElementType::CLASS_CAPACITY_HINT), is created.
+    ///
+    /// ```
+    /// # use tachys::prelude::*;
+    /// # use tachys::html::element::{ElementType, HtmlElement};
+    /// struct Card;
+    ///
+    /// impl ElementType for Card {
+    ///     type Output = web_sys::HtmlDivElement;
+    ///
+    ///     const TAG: &'static str = "div";
+    ///     const SELF_CLOSING: bool = false;
+    ///     const ESCAPE_CHILDREN: bool = true;
+    ///     const NAMESPACE: Option<&'static str> = None;
+    ///     const CLASS_CAPACITY_HINT: usize = 128;
+    ///
+    ///     fn tag(&self) -> &str {
+    ///         Self::TAG
+    ///     }
+    /// }
+    ///
+    /// let card = HtmlElement::new(Card).class("card");
+    /// # #[cfg(feature = "ssr")]
+    /// assert_eq!(card.to_html(), r#"<div class="card"></div>"#);
+    /// ```
+    #[track_caller]
+    pub fn new(tag: E) -> Self {
+        HtmlElement {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: std::panic::Location::caller(),
+            tag,
+            attributes: (),
+            children: (),
+        }
+    }
+}
+
+impl<E, At> HtmlElement<E, At, ()> {
+    /// Sets the element's children to the items of an iterator.
+    ///
+    /// Tuple children added with [`child`](ElementChild::child) have to be known when the code
+    /// is compiled; this is for lists whose length is only known at runtime.
+    ///
+    /// ```
+    /// # use tachys::prelude::*;
+    /// # use tachys::html::element::{li, ul};
+    /// let list = ul().children_from_iter(["a", "b"].map(|item| li().child(item)));
+    /// # #[cfg(feature = "ssr")]
+    /// assert_eq!(list.to_html(), "<ul><li>a</li><li>b</li><!></ul>");
+    /// ```
+    pub fn children_from_iter<I>(
+        self,
+        iter: I,
+    ) -> HtmlElement<E, At, Vec<I::Item>>
+    where
+        I: IntoIterator,
+        I::Item: Render,
+    {
+        self.map_children(|()| iter.into_iter().collect())
+    }
+}
+
 /*impl<E, At, Ch> ElementType for HtmlElement<E, At, Ch>
 where
     E: ElementType,
@@ -262,6 +371,14 @@ where
 }
 
 /// Adds a child to the element.
+///
+/// This is only implemented for elements that can have children. Void elements like `<br>` and
+/// `<img>` can't, so adding a child to one is a compile error:
+///
+/// ```rust,compile_fail
+/// # use tachys::html::element::{br, ElementChild};
+/// let line_break = br().child("text");
+/// ```
 pub trait ElementChild<NewChild>
 where
     NewChild: IntoRender,
@@ -288,6 +405,17 @@ pub trait ElementType: Send + 'static {
     const ESCAPE_CHILDREN: bool;
     /// The element's namespace, if it is not HTML.
     const NAMESPACE: Option<&'static str>;
+    /// Whether the element should carry the render context's
+    /// [`nonce`](crate::ssr::RenderContext::nonce), like `<script>` and `<style>`, so that it is
+    /// allowed by a Content Security Policy.
+    const NEEDS_NONCE: bool = false;
+    /// How many bytes to reserve for the element's `class` attribute before rendering its
+    /// attributes to HTML. Elements that are known to carry many classes can set this to avoid
+    /// growing the buffer one class at a time.
+    const CLASS_CAPACITY_HINT: usize = 0;
+    /// How many bytes to reserve for the element's `style` attribute before rendering its
+    /// attributes to HTML.
+    const STYLE_CAPACITY_HINT: usize = 0;
 
     /// The element's tag.
     fn tag(&self) -> &str;
@@ -347,6 +475,12 @@ where
     }
 
     fn build(self) -> Self::State {
+        #[cfg(feature = "devtools")]
+        crate::devtools::notify(
+            crate::devtools::LifecycleKind::Build,
+            self.tag.tag(),
+        );
+
         let el = Rndr::create_element(self.tag.tag(), E::NAMESPACE);
 
         let attrs = self.attributes.build(&el);
@@ -377,17 +511,18 @@ where
     type Owned = HtmlElement<E, At::CloneableOwned, Ch::Owned>;
 
     const MIN_LENGTH: usize = if E::SELF_CLOSING {
-        3 // < ... />
+        2 // < ... >
         + E::TAG.len()
         + At::MIN_LENGTH
     } else {
         2 // < ... >
         + E::TAG.len()
         + At::MIN_LENGTH
-        + Ch::MIN_LENGTH
+        + if Ch::EXISTS { Ch::MIN_LENGTH } else { 0 }
         + 3 // </ ... >
         + E::TAG.len()
     };
+    const DYNAMIC: bool = Ch::DYNAMIC;
 
     fn dry_resolve(&mut self) {
         self.attributes.dry_resolve();
@@ -406,16 +541,17 @@ where
         }
     }
 
+    // the render context's `nonce` isn't counted, because the element may carry its own
     fn html_len(&self) -> usize {
         if E::SELF_CLOSING {
-            3 // < ... />
+            2 // < ... >
         + E::TAG.len()
         + self.attributes.html_len()
         } else {
             2 // < ... >
         + E::TAG.len()
         + self.attributes.html_len()
-        + self.children.html_len()
+        + if Ch::EXISTS { self.children.html_len() } else { 0 }
         + 3 // </ ... >
         + E::TAG.len()
         }
@@ -429,31 +565,77 @@ where
         mark_branches: bool,
         extra_attributes: Vec<AnyAttribute>,
     ) {
+        let ElementOptions {
+            omit_optional_tags,
+            pretty,
+            default_attributes,
+            max_html_bytes,
+        } = ElementOptions::current();
+        let limit_reached =
+            |buf: &String| max_html_bytes.is_some_and(|max| buf.len() > max);
+        if limit_reached(buf) {
+            return;
+        }
+        if omit_optional_tags {
+            optional_tags::omit_before_sibling(buf, self.tag.tag());
+        }
+        if pretty {
+            pretty::before_open(buf, *position);
+        }
+
         // opening tag
         buf.push('<');
         buf.push_str(self.tag.tag());
 
-        let inner_html =
-            attributes_to_html((self.attributes, extra_attributes), buf);
+        #[cfg(feature = "canonical_attributes")]
+        let attrs_start = buf.len();
+        if default_attributes {
+            ExplicitAttrs::start();
+        }
+        let inner_html = push_attributes(
+            (self.attributes, extra_attributes),
+            buf,
+            E::CLASS_CAPACITY_HINT,
+            E::STYLE_CAPACITY_HINT,
+        );
+        if default_attributes {
+            push_default_attributes(&self.tag, buf, ExplicitAttrs::take());
+        }
+        // sorted after the defaults are added, so that they take their place in the order too
+        #[cfg(feature = "canonical_attributes")]
+        canonical_attrs::sort_from(buf, attrs_start);
 
         buf.push('>');
 
         if !E::SELF_CLOSING {
+            let parent = pretty
+                .then(|| pretty::enter(self.tag.tag(), E::ESCAPE_CHILDREN));
+            *position = Position::FirstChild;
             if !inner_html.is_empty() {
                 buf.push_str(&inner_html);
             } else if Ch::EXISTS {
-                // children
-                *position = Position::FirstChild;
+                // each element decides for its own children, so an ancestor that doesn't escape
+                // its children (like `<noscript>`) doesn't turn escaping off for the elements in
+                // it, which would write text from anywhere in the subtree as markup
                 self.children.to_html_with_buf(
                     buf,
                     position,
                     E::ESCAPE_CHILDREN,
-                    mark_branches,
+                    mark_branches && Ch::DYNAMIC,
                     vec![],
                 );
+                if limit_reached(buf) {
+                    return;
+                }
             }
 
             // closing tag
+            if omit_optional_tags && E::ESCAPE_CHILDREN {
+                optional_tags::omit_before_parent_end(buf, self.tag.tag());
+            }
+            if let Some(parent) = parent {
+                pretty::leave(buf, *position, parent);
+            }
             buf.push_str("</");
             buf.push_str(self.tag.tag());
             buf.push('>');
@@ -471,13 +653,41 @@ where
     ) where
         Self: Sized,
     {
-        let mut buf = String::with_capacity(Self::MIN_LENGTH);
+        buffer.reserve_html_len(&self);
+
+        let ElementOptions {
+            omit_optional_tags,
+            default_attributes,
+            ..
+        } = ElementOptions::current();
+        if omit_optional_tags {
+            buffer.with_buf(|buf| {
+                optional_tags::omit_before_sibling(buf, self.tag.tag())
+            });
+        }
+
+        let mut buf = take_buffer(Self::MIN_LENGTH);
         // opening tag
         buf.push('<');
         buf.push_str(self.tag.tag());
 
-        let inner_html =
-            attributes_to_html((self.attributes, extra_attributes), &mut buf);
+        #[cfg(feature = "canonical_attributes")]
+        let attrs_start = buf.len();
+        if default_attributes {
+            ExplicitAttrs::start();
+        }
+        let inner_html = push_attributes(
+            (self.attributes, extra_attributes),
+            &mut buf,
+            E::CLASS_CAPACITY_HINT,
+            E::STYLE_CAPACITY_HINT,
+        );
+        if default_attributes {
+            push_default_attributes(&self.tag, &mut buf, ExplicitAttrs::take());
+        }
+        // sorted after the defaults are added, so that they take their place in the order too
+        #[cfg(feature = "canonical_attributes")]
+        canonical_attrs::sort_from(&mut buf, attrs_start);
 
         buf.push('>');
         buffer.push_sync(&buf);
@@ -492,18 +702,24 @@ where
                     buffer,
                     position,
                     E::ESCAPE_CHILDREN,
-                    mark_branches,
+                    mark_branches && Ch::DYNAMIC,
                     vec![],
                 );
             }
 
             // closing tag
-            let mut buf = String::with_capacity(3 + E::TAG.len());
+            if omit_optional_tags && E::ESCAPE_CHILDREN {
+                buffer.with_buf(|buf| {
+                    optional_tags::omit_before_parent_end(buf, self.tag.tag())
+                });
+            }
+            buf.clear();
             buf.push_str("</");
             buf.push_str(self.tag.tag());
             buf.push('>');
             buffer.push_sync(&buf);
         }
+        recycle_buffer(buf);
         *position = Position::NextChild;
     }
 
@@ -518,6 +734,12 @@ where
             panic!("Custom elements are not supported in ViewTemplate.");
         }
 
+        #[cfg(feature = "devtools")]
+        crate::devtools::notify(
+            crate::devtools::LifecycleKind::Hydrate,
+            self.tag.tag(),
+        );
+
         // codegen optimisation:
         fn inner_1(
             cursor: &Cursor,
@@ -589,6 +811,12 @@ where
         cursor: &Cursor,
         position: &PositionState,
     ) -> Self::State {
+        #[cfg(feature = "devtools")]
+        crate::devtools::notify(
+            crate::devtools::LifecycleKind::Hydrate,
+            self.tag.tag(),
+        );
+
         // codegen optimisation:
         fn inner_1(
             cursor: &Cursor,
@@ -668,6 +896,39 @@ where
 
 /// Renders an [`Attribute`] (which can be one or more HTML attributes) into an HTML buffer.
 pub fn attributes_to_html<At>(attr: At, buf: &mut String) -> String
+where
+    At: Attribute,
+{
+    attributes_to_html_with_capacity(attr, buf, 0, 0)
+}
+
+/// Renders an [`Attribute`] into an HTML buffer, like [`attributes_to_html`], reserving
+/// `class_capacity` and `style_capacity` bytes for the `class` and `style` values up front.
+pub fn attributes_to_html_with_capacity<At>(
+    attr: At,
+    buf: &mut String,
+    class_capacity: usize,
+    style_capacity: usize,
+) -> String
+where
+    At: Attribute,
+{
+    #[cfg(feature = "canonical_attributes")]
+    let attrs_start = buf.len();
+    let inner_html = push_attributes(attr, buf, class_capacity, style_capacity);
+    #[cfg(feature = "canonical_attributes")]
+    canonical_attrs::sort_from(buf, attrs_start);
+    inner_html
+}
+
+/// Renders an [`Attribute`] into an HTML buffer in the order it was declared, followed by the
+/// `class` and `style` attributes, and returns its inner HTML.
+fn push_attributes<At>(
+    attr: At,
+    buf: &mut String,
+    class_capacity: usize,
+    style_capacity: usize,
+) -> String
 where
     At: Attribute,
 {
@@ -679,9 +940,9 @@ where
     // added
 
     // String doesn't allocate until the first push, so this is cheap if there
-    // is no class or style on an element
-    let mut class = String::new();
-    let mut style = String::new();
+    // is no class or style on an element (and no capacity hint)
+    let mut class = String::with_capacity(class_capacity);
+    let mut style = String::with_capacity(style_capacity);
     let mut inner_html = String::new();
 
     // inject regular attributes, and fill class and style
@@ -703,6 +964,61 @@ where
     inner_html
 }
 
+/// Adds the attributes the render context asks for to an element, unless the element already
+/// sets them, as recorded in `explicit` while its attributes were rendered:
+/// - `loading="lazy"` on an `<img>` or `<iframe>`, with
+///   [`default_lazy_loading`](RenderContext::default_lazy_loading)
+/// - `lang` and `dir` on the `<html>` element, with [`locale`](RenderContext::locale)
+/// - `nonce` on elements that [need one](ElementType::NEEDS_NONCE), with
+///   [`nonce`](RenderContext::nonce)
+/// - `data-testid` on every element, with [`auto_testid`](RenderContext::auto_testid)
+fn push_default_attributes<E: ElementType>(
+    el: &E,
+    buf: &mut String,
+    explicit: ExplicitAttrs,
+) {
+    RenderContext::with_current(|cx| {
+        if E::NEEDS_NONCE {
+            if let Some(nonce) = &cx.nonce {
+                if !explicit.contains(DefaultAttr::Nonce) {
+                    buf.push_str(" nonce=\"");
+                    buf.push_str(&escape_attr(nonce));
+                    buf.push('"');
+                }
+            }
+        }
+        match el.tag() {
+            "img" | "iframe"
+                if cx.default_lazy_loading
+                    && !explicit.contains(DefaultAttr::Loading) =>
+            {
+                buf.push_str(" loading=\"lazy\"");
+            }
+            "html" => {
+                if let Some(locale) = &cx.locale {
+                    if !explicit.contains(DefaultAttr::Lang) {
+                        buf.push_str(" lang=\"");
+                        buf.push_str(&escape_attr(locale));
+                        buf.push('"');
+                    }
+                    if !explicit.contains(DefaultAttr::Dir) && cx.is_rtl() {
+                        buf.push_str(" dir=\"rtl\"");
+                    }
+                }
+            }
+            _ => {}
+        }
+        if cx.auto_testid && !explicit.contains(DefaultAttr::TestId) {
+            _ = write!(
+                buf,
+                " data-testid=\"{}-{}\"",
+                escape_attr(el.tag()),
+                next_auto_testid()
+            );
+        }
+    })
+}
+
 /// The retained view state for an HTML element.
 pub struct ElementState<At, Ch> {
     pub(crate) el: crate::renderer::types::Element,
@@ -710,6 +1026,37 @@ pub struct ElementState<At, Ch> {
     pub(crate) children: Option<Ch>,
 }
 
+impl<At, Ch> ElementState<At, Ch>
+where
+    Ch: Mountable,
+{
+    /// Replaces all of the element's children with a newly built view, without comparing it to
+    /// the current children.
+    ///
+    /// [`rebuild`](Render::rebuild) reconciles the new children with the old ones node by node.
+    /// When the whole subtree is known to have changed, that work is wasted: this unmounts the
+    /// old children without comparing them to anything, and mounts the new ones instead.
+    ///
+    /// The element's state keeps the type of its children, so the new children have to build
+    /// the same type of state as the current ones. Children that can change type, like a
+    /// different component depending on some condition, should be an
+    /// [`Either`](either_of::Either) or an [`AnyView`](crate::view::any_view::AnyView) to
+    /// begin with.
+    pub fn replace_children<NewCh>(&mut self, children: NewCh)
+    where
+        NewCh: Render<State = Ch>,
+    {
+        match self.children.take() {
+            Some(mut old) => old.unmount(),
+            // children set with `inner_html` have no state to unmount
+            None => Rndr::clear_children(&self.el),
+        }
+        let mut children = children.build();
+        children.mount(&self.el, None);
+        self.children = Some(children);
+    }
+}
+
 impl<At, Ch> Deref for ElementState<At, Ch> {
     type Target = crate::renderer::types::Element;
 
@@ -720,6 +1067,11 @@ impl<At, Ch> Deref for ElementState<At, Ch> {
 
 impl<At, Ch> Mountable for ElementState<At, Ch> {
     fn unmount(&mut self) {
+        #[cfg(feature = "devtools")]
+        crate::devtools::notify(
+            crate::devtools::LifecycleKind::Unmount,
+            &self.el.local_name(),
+        );
         Rndr::remove(&self.el);
     }
 
@@ -729,6 +1081,11 @@ impl<At, Ch> Mountable for ElementState<At, Ch> {
         marker: Option<&crate::renderer::types::Node>,
     ) {
         Rndr::insert_node(parent, &self.el, marker);
+        #[cfg(feature = "devtools")]
+        crate::devtools::notify(
+            crate::devtools::LifecycleKind::Mount,
+            &self.el.local_name(),
+        );
     }
 
     fn try_mount(
@@ -736,7 +1093,15 @@ impl<At, Ch> Mountable for ElementState<At, Ch> {
         parent: &crate::renderer::types::Element,
         marker: Option<&crate::renderer::types::Node>,
     ) -> bool {
-        Rndr::try_insert_node(parent, &self.el, marker)
+        let mounted = Rndr::try_insert_node(parent, &self.el, marker);
+        #[cfg(feature = "devtools")]
+        if mounted {
+            crate::devtools::notify(
+                crate::devtools::LifecycleKind::Mount,
+                &self.el.local_name(),
+            );
+        }
+        mounted
     }
 
     fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
@@ -915,3 +1280,56 @@ mod tests {
     }
 }
  */
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{div, em, noscript, p, style, ElementChild};
+    use crate::{
+        html::attribute::global::GlobalAttributes,
+        view::{Position, RenderHtml},
+    };
+
+    #[test]
+    fn text_is_escaped_unless_an_element_opts_out() {
+        assert_eq!(p().child("a < b").to_html(), "<p>a &lt; b</p>");
+        assert_eq!(style().child("a < b").to_html(), "<style>a < b</style>");
+    }
+
+    #[test]
+    fn escape_flag_only_applies_to_text_passed_directly() {
+        fn render_unescaped(view: impl RenderHtml) -> String {
+            let mut buf = String::new();
+            view.to_html_with_buf(
+                &mut buf,
+                &mut Position::FirstChild,
+                false,
+                false,
+                vec![],
+            );
+            buf
+        }
+
+        assert_eq!(render_unescaped("a < b"), "a < b");
+        assert_eq!(
+            render_unescaped(div().child(p().child("a < b"))),
+            "<div><p>a &lt; b</p></div>"
+        );
+    }
+
+    #[test]
+    fn elements_inside_raw_elements_escape_their_text() {
+        assert_eq!(
+            noscript().child(p().child("a < b")).to_html(),
+            "<noscript><p>a &lt; b</p></noscript>"
+        );
+    }
+
+    #[test]
+    fn map_children_keeps_tag_and_attributes() {
+        let el = p()
+            .id("greeting")
+            .child("Hello")
+            .map_children(|(hello,)| (hello, em().child("world")));
+        assert_eq!(el.to_html(), r#"<p id="greeting">Hello<em>world</em></p>"#);
+    }
+}
@@ -0,0 +1,142 @@
+//! Closing tags that can be omitted, following the rules in the HTML spec:
+//! <https://html.spec.whatwg.org/multipage/syntax.html#optional-tags>
+
+/// Elements whose closing tag can be omitted if the next sibling is one of the listed elements.
+const FOLLOWED_BY: &[(&str, &[&str])] = &[
+    ("li", &["li"]),
+    (
+        "p",
+        &[
+            "address",
+            "article",
+            "aside",
+            "blockquote",
+            "details",
+            "dialog",
+            "div",
+            "dl",
+            "fieldset",
+            "figcaption",
+            "figure",
+            "footer",
+            "form",
+            "h1",
+            "h2",
+            "h3",
+            "h4",
+            "h5",
+            "h6",
+            "header",
+            "hgroup",
+            "hr",
+            "main",
+            "menu",
+            "nav",
+            "ol",
+            "p",
+            "pre",
+            "search",
+            "section",
+            "table",
+            "ul",
+        ],
+    ),
+    ("td", &["td", "th"]),
+    ("th", &["td", "th"]),
+];
+
+/// Elements whose closing tag can be omitted if there is no more content in the parent.
+const AT_PARENT_END: &[&str] = &["li", "p", "td", "th"];
+
+/// A `<p>` can only be implicitly closed by its parent's closing tag if the parent is not one of
+/// these elements or an autonomous custom element.
+const P_PARENT_EXCEPTIONS: &[&str] =
+    &["a", "audio", "del", "ins", "map", "noscript", "video"];
+
+/// If the buffer ends with a closing tag that is implied by the opening tag of `next_tag`,
+/// removes it.
+pub(crate) fn omit_before_sibling(buf: &mut String, next_tag: &str) {
+    for (tag, followers) in FOLLOWED_BY {
+        if followers.contains(&next_tag) && truncate_closing_tag(buf, tag) {
+            return;
+        }
+    }
+}
+
+/// If the buffer ends with a closing tag that is implied by the closing tag of `parent_tag`,
+/// removes it.
+pub(crate) fn omit_before_parent_end(buf: &mut String, parent_tag: &str) {
+    for tag in AT_PARENT_END {
+        if *tag == "p"
+            && (P_PARENT_EXCEPTIONS.contains(&parent_tag)
+                || parent_tag.contains('-'))
+        {
+            continue;
+        }
+        if truncate_closing_tag(buf, tag) {
+            return;
+        }
+    }
+}
+
+fn truncate_closing_tag(buf: &mut String, tag: &str) -> bool {
+    let closing_len = tag.len() + 3;
+    let ends_with_tag = buf
+        .strip_suffix('>')
+        .and_then(|rest| rest.strip_suffix(tag))
+        .is_some_and(|rest| rest.ends_with("</"));
+    if ends_with_tag {
+        buf.truncate(buf.len() - closing_len);
+    }
+    ends_with_tag
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use crate::{
+        html::element::{custom, div, li, p, ul, ElementChild},
+        ssr::RenderContext,
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn list_items_omit_closing_tags() {
+        let html = RenderContext::new().omit_optional_tags(true).run(|| {
+            ul().child((li().child("a"), li().child("b"), li().child("c")))
+                .to_html()
+        });
+        assert_eq!(html, "<ul><li>a<li>b<li>c</ul>");
+    }
+
+    #[test]
+    fn closing_tags_are_kept_by_default() {
+        let html = ul().child((li().child("a"), li().child("b"))).to_html();
+        assert_eq!(html, "<ul><li>a</li><li>b</li></ul>");
+    }
+
+    #[test]
+    fn paragraph_keeps_closing_tag_before_phrasing_content() {
+        let html = RenderContext::new().omit_optional_tags(true).run(|| {
+            div()
+                .child((p().child("a"), "text", p().child("b")))
+                .to_html()
+        });
+        assert_eq!(html, "<div><p>a</p>text<p>b</div>");
+    }
+
+    #[test]
+    fn paragraph_keeps_closing_tag_at_end_of_custom_element() {
+        let html = RenderContext::new().omit_optional_tags(true).run(|| {
+            div()
+                .child((
+                    custom("my-card").child(p().child("a")),
+                    div().child(p().child("b")),
+                ))
+                .to_html()
+        });
+        assert_eq!(
+            html,
+            "<div><my-card><p>a</p></my-card><div><p>b</div></div>"
+        );
+    }
+}
@@ -0,0 +1,113 @@
+//! Indents the HTML rendered for [`RenderContext::pretty`](crate::ssr::RenderContext::pretty).
+//!
+//! An element starts a new line, indented by two spaces per level of nesting, unless it directly
+//! follows text: breaking a line there would add whitespace to the text. If any child of an
+//! element started a line and the children end with an element, the closing tag gets its own line
+//! as well. Nothing is added inside elements whose whitespace is significant, like `<pre>`, or
+//! whose children are not HTML, like `<script>`.
+
+use crate::view::Position;
+use std::cell::Cell;
+
+const INDENT: &str = "  ";
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    static PRESERVE: Cell<bool> = const { Cell::new(false) };
+    static LINE_BROKEN: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The state of the parent element, which is restored by [`leave`].
+pub(crate) struct Parent {
+    depth: usize,
+    preserve: bool,
+    line_broken: bool,
+}
+
+/// Starts a new line for an element that is about to be opened, if it should get one.
+pub(crate) fn before_open(buf: &mut String, position: Position) {
+    if PRESERVE.get()
+        || buf.is_empty()
+        || matches!(position, Position::NextChildAfterText)
+    {
+        return;
+    }
+    new_line(buf, DEPTH.get());
+    LINE_BROKEN.set(true);
+}
+
+/// Moves one level deeper, for the children of the element with the given tag.
+pub(crate) fn enter(tag: &str, escape_children: bool) -> Parent {
+    let parent = Parent {
+        depth: DEPTH.get(),
+        preserve: PRESERVE.get(),
+        line_broken: LINE_BROKEN.replace(false),
+    };
+    DEPTH.set(parent.depth + 1);
+    PRESERVE.set(
+        parent.preserve
+            || !escape_children
+            || matches!(tag, "pre" | "textarea"),
+    );
+    parent
+}
+
+/// Moves back to the parent's level after its children were rendered, starting a new line for
+/// the closing tag if the children were broken into lines.
+pub(crate) fn leave(buf: &mut String, position: Position, parent: Parent) {
+    if LINE_BROKEN.get()
+        && !PRESERVE.get()
+        && !matches!(position, Position::NextChildAfterText)
+    {
+        new_line(buf, parent.depth);
+    }
+    DEPTH.set(parent.depth);
+    PRESERVE.set(parent.preserve);
+    LINE_BROKEN.set(parent.line_broken);
+}
+
+fn new_line(buf: &mut String, depth: usize) {
+    buf.push('\n');
+    for _ in 0..depth {
+        buf.push_str(INDENT);
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use crate::{
+        html::element::{em, li, main, p, pre, span, ul, ElementChild},
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn indents_nested_elements() {
+        let view = main().child(p().child((
+            "Hello, ",
+            em().child("beautiful"),
+            " world!",
+        )));
+        assert_eq!(
+            view.to_html_pretty(),
+            "<main>\n  <p>Hello, <em>beautiful</em> world!</p>\n</main>"
+        );
+    }
+
+    #[test]
+    fn preserves_whitespace_in_pre() {
+        let view = main().child((
+            ul().child((li().child("a"), li().child("b"))),
+            pre().child(span().child("x")),
+        ));
+        assert_eq!(
+            view.to_html_pretty(),
+            "<main>
+  <ul>
+    <li>a</li>
+    <li>b</li>
+  </ul>
+  <pre><span>x</span></pre>
+</main>"
+        );
+    }
+}
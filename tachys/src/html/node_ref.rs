@@ -25,6 +25,12 @@ where
 {
     /// Fills the container with the element.
     fn load(self, el: &crate::renderer::types::Element);
+
+    /// Empties the container, if it still holds the element.
+    ///
+    /// This is called when the state of the element it was loaded with is dropped, after the
+    /// element has been unmounted. The default implementation does nothing.
+    fn unload(self, _el: &crate::renderer::types::Element) {}
 }
 
 /// An [`Attribute`] that will fill a [`NodeRefContainer`] with an HTML element.
@@ -58,6 +64,30 @@ where
     }
 }
 
+/// Retained view state for a [`NodeRefAttr`].
+///
+/// Dropping it [unloads](NodeRefContainer::unload) the element from the container.
+#[derive(Debug)]
+pub struct NodeRefState<E, C>
+where
+    E: ElementType,
+    C: NodeRefContainer<E>,
+{
+    el: crate::renderer::types::Element,
+    container: C,
+    ty: PhantomData<E>,
+}
+
+impl<E, C> Drop for NodeRefState<E, C>
+where
+    E: ElementType,
+    C: NodeRefContainer<E>,
+{
+    fn drop(&mut self) {
+        self.container.clone().unload(&self.el);
+    }
+}
+
 impl<E, C> Attribute for NodeRefAttr<E, C>
 where
     E: ElementType,
@@ -67,7 +97,7 @@ where
 {
     const MIN_LENGTH: usize = 0;
     type AsyncOutput = Self;
-    type State = crate::renderer::types::Element;
+    type State = NodeRefState<E, C>;
     type Cloneable = Self;
     type CloneableOwned = Self;
 
@@ -89,17 +119,21 @@ where
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
-        self.container.load(el);
-        el.to_owned()
+        self.build(el)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        self.container.load(el);
-        el.to_owned()
+        self.container.clone().load(el);
+        NodeRefState {
+            el: el.to_owned(),
+            container: self.container,
+            ty: PhantomData,
+        }
     }
 
     fn rebuild(self, state: &mut Self::State) {
-        self.container.load(state);
+        self.container.clone().load(&state.el);
+        state.container = self.container;
     }
 
     fn into_cloneable(self) -> Self::Cloneable {
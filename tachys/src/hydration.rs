@@ -15,12 +15,21 @@ const COMMENT_NODE: u16 = 8;
 /// This cursor tracks the location in the DOM that is currently being hydrated. Each that type
 /// implements [`RenderHtml`](crate::view::RenderHtml) knows how to advance the cursor to access
 /// the nodes it needs.
+///
+/// For debugging hydration mismatches, the cursor can also record the moves it makes; see
+/// [`Cursor::start_trace`].
 #[derive(Debug)]
-pub struct Cursor(Rc<RefCell<crate::renderer::types::Node>>);
+pub struct Cursor {
+    node: Rc<RefCell<crate::renderer::types::Node>>,
+    trace: Rc<RefCell<Option<Vec<Position>>>>,
+}
 
 impl Clone for Cursor {
     fn clone(&self) -> Self {
-        Self(Rc::clone(&self.0))
+        Self {
+            node: Rc::clone(&self.node),
+            trace: Rc::clone(&self.trace),
+        }
     }
 }
 
@@ -34,38 +43,52 @@ where
             crate::renderer::types::Node,
         >>::as_ref(&root)
         .clone();
-        Self(Rc::new(RefCell::new(root)))
+        Self {
+            node: Rc::new(RefCell::new(root)),
+            trace: Default::default(),
+        }
+    }
+
+    /// Starts recording the moves made by this cursor (and its clones), discarding any trace
+    /// recorded so far.
+    ///
+    /// Each call to [`child`](Cursor::child) is recorded as [`Position::FirstChild`], each call to
+    /// [`sibling`](Cursor::sibling) as [`Position::NextChild`], and each call to
+    /// [`set`](Cursor::set) as [`Position::Current`]. Moves to a [`parent`](Cursor::parent) are
+    /// not recorded.
+    pub fn start_trace(&self) {
+        *self.trace.borrow_mut() = Some(Vec::new());
+    }
+
+    /// Returns the moves recorded since [`start_trace`](Cursor::start_trace) was called, or an
+    /// empty path if it was never called.
+    pub fn trace(&self) -> Vec<Position> {
+        self.trace.borrow().clone().unwrap_or_default()
+    }
+
+    fn record(&self, position: Position) {
+        if let Some(trace) = self.trace.borrow_mut().as_mut() {
+            trace.push(position);
+        }
     }
 
     /// Returns the node at which the cursor is currently located.
     pub fn current(&self) -> crate::renderer::types::Node {
-        self.0.borrow().clone()
+        self.node.borrow().clone()
     }
 
     /// Advances to the next child of the node at which the cursor is located.
     ///
     /// Does nothing if there is no child.
     pub fn child(&self) {
-        let mut inner = self.0.borrow_mut();
+        self.record(Position::FirstChild);
+        let mut inner = self.node.borrow_mut();
         if let Some(node) = Rndr::first_child(&inner) {
             *inner = node;
         }
 
         #[cfg(feature = "mark_branches")]
-        {
-            while inner.node_type() == COMMENT_NODE {
-                if let Some(content) = inner.text_content() {
-                    if content.starts_with("bo") || content.starts_with("bc") {
-                        if let Some(sibling) = Rndr::next_sibling(&inner) {
-                            *inner = sibling;
-                            continue;
-                        }
-                    }
-                }
-
-                break;
-            }
-        }
+        skip_branch_markers(&mut inner);
         // //drop(inner);
         //crate::log(">> which is ");
         //Rndr::log_node(&self.current());
@@ -75,35 +98,42 @@ where
     ///
     /// Does nothing if there is no sibling.
     pub fn sibling(&self) {
-        let mut inner = self.0.borrow_mut();
+        self.record(Position::NextChild);
+        let mut inner = self.node.borrow_mut();
         if let Some(node) = Rndr::next_sibling(&inner) {
             *inner = node;
         }
 
         #[cfg(feature = "mark_branches")]
-        {
-            while inner.node_type() == COMMENT_NODE {
-                if let Some(content) = inner.text_content() {
-                    if content.starts_with("bo") || content.starts_with("bc") {
-                        if let Some(sibling) = Rndr::next_sibling(&inner) {
-                            *inner = sibling;
-                            continue;
-                        }
-                    }
-                }
-                break;
-            }
-        }
+        skip_branch_markers(&mut inner);
         //drop(inner);
         //crate::log(">> which is ");
         //Rndr::log_node(&self.current());
     }
 
+    /// Advances past `n` siblings of the node at which the cursor is located, as if
+    /// [`sibling`](Cursor::sibling) had been called `n` times.
+    ///
+    /// Does nothing if `n` is `0`. If there are fewer than `n` siblings, stops at the last one.
+    pub fn sibling_n(&self, n: usize) {
+        let mut inner = self.node.borrow_mut();
+        for _ in 0..n {
+            let Some(node) = Rndr::next_sibling(&inner) else {
+                break;
+            };
+            self.record(Position::NextChild);
+            *inner = node;
+
+            #[cfg(feature = "mark_branches")]
+            skip_branch_markers(&mut inner);
+        }
+    }
+
     /// Moves to the parent of the node at which the cursor is located.
     ///
     /// Does nothing if there is no parent.
     pub fn parent(&self) {
-        let mut inner = self.0.borrow_mut();
+        let mut inner = self.node.borrow_mut();
         if let Some(node) = Rndr::get_parent(&inner) {
             *inner = node;
         }
@@ -111,7 +141,8 @@ where
 
     /// Sets the cursor to some node.
     pub fn set(&self, node: crate::renderer::types::Node) {
-        *self.0.borrow_mut() = node;
+        self.record(Position::Current);
+        *self.node.borrow_mut() = node;
     }
 
     /// Advances to the next placeholder node and returns it
@@ -138,6 +169,23 @@ where
     }
 }
 
+/// Moves past any branch markers, which are not hydrated themselves.
+#[cfg(feature = "mark_branches")]
+fn skip_branch_markers(node: &mut crate::renderer::types::Node) {
+    while node.node_type() == COMMENT_NODE {
+        if let Some(content) = node.text_content() {
+            if content.starts_with("bo") || content.starts_with("bc") {
+                if let Some(sibling) = Rndr::next_sibling(node) {
+                    *node = sibling;
+                    continue;
+                }
+            }
+        }
+
+        break;
+    }
+}
+
 #[cfg(any(debug_assertions, leptos_debuginfo))]
 thread_local! {
     static CURRENTLY_HYDRATING: Cell<Option<&'static Location<'static>>> = const { Cell::new(None) };
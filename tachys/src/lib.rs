@@ -46,6 +46,9 @@ pub mod prelude {
 use wasm_bindgen::JsValue;
 use web_sys::Node;
 
+/// Hooks for building developer tools, like element lifecycle observers.
+#[cfg(feature = "devtools")]
+pub mod devtools;
 /// Helpers for interacting with the DOM.
 pub mod dom;
 /// Types for building a statically-typed HTML view tree.
@@ -60,6 +63,9 @@ pub mod renderer;
 pub mod ssr;
 /// Types for SVG.
 pub mod svg;
+/// Utilities for testing views.
+#[cfg(feature = "testing")]
+pub mod testing;
 /// Core logic for manipulating views.
 pub mod view;
 
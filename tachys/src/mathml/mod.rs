@@ -168,3 +168,25 @@ mathml_elements![
     annotation [],
     semantics [],
 ];
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{math, mfrac, mn, mrow};
+    use crate::{html::element::ElementChild, view::RenderHtml};
+
+    #[test]
+    fn renders_nested_elements() {
+        let view =
+            math().child(mfrac().child((mn().child("1"), mn().child("2"))));
+        let len = view.html_len();
+        let html = view.to_html();
+        assert_eq!(html, "<math><mfrac><mn>1</mn><mn>2</mn></mfrac></math>");
+        assert_eq!(len, html.len());
+    }
+
+    #[test]
+    fn escapes_text_children() {
+        let html = mrow().child("a < b").to_html();
+        assert_eq!(html, "<mrow>a &lt; b</mrow>");
+    }
+}
@@ -2,7 +2,10 @@ use crate::{
     html::{
         attribute::{any_attribute::AnyAttribute, AttributeValue},
         class::IntoClass,
-        element::InnerHtmlValue,
+        element::{
+            current_sanitizer, sanitize, set_sanitized_inner_html,
+            InnerHtmlValue, Sanitizer,
+        },
         property::IntoProperty,
         style::IntoStyle,
     },
@@ -345,7 +348,7 @@ impl IntoStyle for Oco<'static, str> {
 
 impl InnerHtmlValue for Oco<'static, str> {
     type AsyncOutput = Self;
-    type State = (crate::renderer::types::Element, Self);
+    type State = (crate::renderer::types::Element, Self, Option<Sanitizer>);
     type Cloneable = Self;
     type CloneableOwned = Self;
 
@@ -354,7 +357,7 @@ impl InnerHtmlValue for Oco<'static, str> {
     }
 
     fn to_html(self, buf: &mut String) {
-        buf.push_str(&self);
+        buf.push_str(&sanitize(current_sanitizer(), &self));
     }
 
     fn to_template(_buf: &mut String) {}
@@ -363,20 +366,22 @@ impl InnerHtmlValue for Oco<'static, str> {
         self,
         el: &crate::renderer::types::Element,
     ) -> Self::State {
+        let sanitizer = current_sanitizer();
         if !FROM_SERVER {
-            Rndr::set_inner_html(el, &self);
+            set_sanitized_inner_html(el, &self, sanitizer);
         }
-        (el.clone(), self)
+        (el.clone(), self, sanitizer)
     }
 
     fn build(self, el: &crate::renderer::types::Element) -> Self::State {
-        Rndr::set_inner_html(el, &self);
-        (el.clone(), self)
+        let sanitizer = current_sanitizer();
+        set_sanitized_inner_html(el, &self, sanitizer);
+        (el.clone(), self, sanitizer)
     }
 
     fn rebuild(self, state: &mut Self::State) {
         if self != state.1 {
-            Rndr::set_inner_html(&state.0, &self);
+            set_sanitized_inner_html(&state.0, &self, state.2);
             state.1 = self;
         }
     }
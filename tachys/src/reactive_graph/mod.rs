@@ -128,6 +128,9 @@ where
     }
 }
 
+// On the server, a function is called once and its result is rendered to HTML. It is not
+// reactive there: nothing re-runs it when the signals it reads change. In the browser, it is
+// re-run inside a render effect whenever its dependencies change (see the `Render` impl above).
 impl<F, V> RenderHtml for F
 where
     F: ReactiveFunction<Output = V>,
@@ -991,6 +994,20 @@ mod reactive_stores {
     reactive_impl!(ArcField, <V>, V, false, ArcField<V>: Get<Value = V>);
 }
 
+#[cfg(all(test, feature = "ssr"))]
+mod ssr_tests {
+    use crate::{
+        html::element::{div, p, ElementChild},
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn closure_renders_as_child() {
+        let html = div().child(move || p().child("x")).to_html();
+        assert_eq!(html, "<div><p>x</p></div>");
+    }
+}
+
 /*
 #[cfg(test)]
 mod tests {
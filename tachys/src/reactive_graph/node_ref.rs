@@ -8,12 +8,12 @@ use reactive_graph::{
     },
     traits::{
         DefinedAt, Get, IsDisposed, Notify, ReadUntracked, Set, Track,
-        UntrackableGuard, Write,
+        UntrackableGuard, WithUntracked, Write,
     },
 };
 use send_wrapper::SendWrapper;
 use std::{cell::Cell, ops::DerefMut};
-use wasm_bindgen::JsCast;
+use wasm_bindgen::{JsCast, JsValue};
 
 /// A reactive reference to a DOM node that can be used with the `node_ref` attribute.
 #[derive(Debug)]
@@ -94,6 +94,22 @@ where
         self.0
             .set(Some(SendWrapper::new(el.clone().unchecked_into())));
     }
+
+    fn unload(self, el: &crate::renderer::types::Element) {
+        // another element may have been loaded since, which should be kept
+        let holds_el = self
+            .0
+            .try_with_untracked(|current| {
+                current.as_deref().is_some_and(|current| {
+                    AsRef::<JsValue>::as_ref(current)
+                        == AsRef::<JsValue>::as_ref(el)
+                })
+            })
+            .unwrap_or(false);
+        if holds_el {
+            self.0.try_set(None);
+        }
+    }
 }
 
 impl<E> DefinedAt for NodeRef<E>
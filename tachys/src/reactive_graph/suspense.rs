@@ -350,7 +350,7 @@ where
 
                     // out-of-order streams immediately push fallback,
                     // wrapped by suspense markers
-                    if OUT_OF_ORDER {
+                    if OUT_OF_ORDER && buf.claim_out_of_order_boundary() {
                         let mut fallback_position = *position;
                         buf.push_fallback::<()>(
                             (),
@@ -371,11 +371,11 @@ where
                             extra_attrs,
                         );
                     } else {
+                        let mut builder = buf.sub_builder(id);
                         buf.push_async({
                             let mut position = *position;
                             async move {
                                 let value = fut.await;
-                                let mut builder = StreamBuilder::new(id);
                                 value.to_html_async_with_buf::<OUT_OF_ORDER>(
                                     &mut builder,
                                     &mut position,
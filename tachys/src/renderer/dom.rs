@@ -122,6 +122,20 @@ impl Dom {
         or_debug!(node.remove_attribute(name), node, "removeAttribute");
     }
 
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
+    pub fn set_attribute_ns(
+        node: &Element,
+        namespace: &str,
+        name: &str,
+        value: &str,
+    ) {
+        or_debug!(
+            node.set_attribute_ns(Some(namespace), name, value),
+            node,
+            "setAttributeNS"
+        );
+    }
+
     #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace"))]
     pub fn insert_node(
         parent: &Element,
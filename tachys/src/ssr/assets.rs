@@ -0,0 +1,101 @@
+//! Collects the URLs referenced by rendered HTML, for
+//! [`RenderHtml::to_html_with_assets`](crate::view::RenderHtml::to_html_with_assets).
+
+use std::cell::{Cell, RefCell};
+
+thread_local! {
+    static ASSETS: RefCell<Option<Vec<AssetRef>>> = const { RefCell::new(None) };
+    static COLLECTING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Whether the values of attributes with this name are reported as assets.
+pub(crate) const fn is_asset_attribute(key: &str) -> bool {
+    matches!(key.as_bytes(), b"href" | b"src")
+}
+
+/// Whether assets are being collected on this thread, which is cheaper to check than taking the
+/// collected assets.
+pub(crate) fn collecting_assets() -> bool {
+    COLLECTING.get()
+}
+
+/// A URL referenced by an attribute of the rendered HTML.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetRef {
+    /// The name of the attribute, like `href` or `src`.
+    pub attribute: &'static str,
+    /// The URL, as it was given to the attribute.
+    pub url: String,
+}
+
+/// Runs the function, returning each distinct asset that is rendered while it runs, in the order
+/// they were first rendered.
+pub(crate) fn collect_assets<T>(fun: impl FnOnce() -> T) -> (T, Vec<AssetRef>) {
+    let prev = ASSETS.replace(Some(Vec::new()));
+    let prev_collecting = COLLECTING.replace(true);
+    let value = fun();
+    COLLECTING.set(prev_collecting);
+    let assets = ASSETS.replace(prev).unwrap_or_default();
+    (value, assets)
+}
+
+/// Records an asset, if assets are being collected.
+///
+/// `key` is an [asset attribute](is_asset_attribute), and `html` is the attribute as it was
+/// rendered, like ` src="a.png"`, which is empty if the attribute was left out.
+pub(crate) fn record_asset(key: &'static str, html: &str) {
+    let Some(url) = html
+        .strip_prefix(' ')
+        .and_then(|html| html.strip_prefix(key))
+        .and_then(|html| html.strip_prefix("=\""))
+        .and_then(|html| html.strip_suffix('"'))
+    else {
+        return;
+    };
+    ASSETS.with_borrow_mut(|assets| {
+        if let Some(assets) = assets {
+            let asset = AssetRef {
+                attribute: key,
+                url: html_escape::decode_html_entities(url).into_owned(),
+            };
+            if !assets.contains(&asset) {
+                assets.push(asset);
+            }
+        }
+    });
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::AssetRef;
+    use crate::{
+        html::element::{a, body, head, html, img, link, ElementChild},
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn reports_stylesheet_and_image_urls() {
+        let page = html().child((
+            head().child(link().rel("stylesheet").href("/style.css")),
+            body().child((
+                img().src("/logo.png?v=1&size=2"),
+                a().href("/style.css"),
+            )),
+        ));
+        let (html, assets) = page.to_html_with_assets();
+        assert!(html.contains(r#"src="/logo.png?v=1&amp;size=2""#));
+        assert_eq!(
+            assets,
+            [
+                AssetRef {
+                    attribute: "href",
+                    url: "/style.css".to_string(),
+                },
+                AssetRef {
+                    attribute: "src",
+                    url: "/logo.png?v=1&size=2".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,887 @@
+use crate::html::element::Sanitizer;
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    error::Error,
+    fmt,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+thread_local! {
+    static CURRENT: RefCell<RenderContext> = RefCell::new(RenderContext::default());
+    static LIMIT_EXCEEDED: Cell<Option<usize>> = const { Cell::new(None) };
+    static COUNTERS: RefCell<Arc<Counters>> = RefCell::default();
+    static ELEMENT_OPTIONS: Cell<ElementOptions> = const { Cell::new(ElementOptions::NONE) };
+    static EXPLICIT_ATTRS: Cell<u8> = const { Cell::new(0) };
+}
+
+/// The options that every element checks while it is rendered, copied out of the current
+/// context so that checking them doesn't borrow it.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ElementOptions {
+    pub(crate) omit_optional_tags: bool,
+    pub(crate) pretty: bool,
+    /// Whether an element may get an attribute that it doesn't set itself.
+    pub(crate) default_attributes: bool,
+    pub(crate) max_html_bytes: Option<usize>,
+}
+
+impl ElementOptions {
+    /// The options of the default render context.
+    const NONE: Self = Self {
+        omit_optional_tags: false,
+        pretty: false,
+        default_attributes: false,
+        max_html_bytes: None,
+    };
+
+    fn of(cx: &RenderContext) -> Self {
+        Self {
+            omit_optional_tags: cx.omit_optional_tags,
+            pretty: cx.pretty,
+            default_attributes: cx.nonce.is_some()
+                || cx.default_lazy_loading
+                || cx.locale.is_some()
+                || cx.auto_testid,
+            max_html_bytes: cx.max_html_bytes,
+        }
+    }
+
+    /// The options of the current render context.
+    pub(crate) fn current() -> Self {
+        ELEMENT_OPTIONS.get()
+    }
+}
+
+/// An attribute that the render context can add to elements that don't set it themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DefaultAttr {
+    Loading = 1,
+    Lang = 1 << 1,
+    Dir = 1 << 2,
+    Nonce = 1 << 3,
+    TestId = 1 << 4,
+}
+
+impl DefaultAttr {
+    /// The default attribute with the given name, if any.
+    pub(crate) const fn from_key(key: &str) -> Option<Self> {
+        match key.as_bytes() {
+            b"loading" => Some(Self::Loading),
+            b"lang" => Some(Self::Lang),
+            b"dir" => Some(Self::Dir),
+            b"nonce" => Some(Self::Nonce),
+            b"data-testid" => Some(Self::TestId),
+            _ => None,
+        }
+    }
+
+    /// Records that the element whose attributes are being rendered sets this attribute itself.
+    pub(crate) fn set_explicitly(self) {
+        EXPLICIT_ATTRS.set(EXPLICIT_ATTRS.get() | self as u8);
+    }
+}
+
+/// The [default attributes](DefaultAttr) that an element sets itself, which are recorded while
+/// its attributes are rendered.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ExplicitAttrs(u8);
+
+impl ExplicitAttrs {
+    /// Starts recording the default attributes set by the element that is about to be rendered.
+    pub(crate) fn start() {
+        EXPLICIT_ATTRS.set(0);
+    }
+
+    /// Returns the default attributes that were set since [`start`](ExplicitAttrs::start).
+    pub(crate) fn take() -> Self {
+        Self(EXPLICIT_ATTRS.take())
+    }
+
+    pub(crate) fn contains(self, attr: DefaultAttr) -> bool {
+        self.0 & attr as u8 != 0
+    }
+}
+
+/// The counters that number the ids generated during a render.
+///
+/// They are shared with every [`CapturedContext`] taken during the render, so that the parts of a
+/// view that are rendered later don't reuse ids.
+#[derive(Debug, Default)]
+struct Counters {
+    testid: AtomicUsize,
+    fragment_id: AtomicUsize,
+}
+
+/// Options that change how views are rendered to HTML.
+///
+/// A render context is set for the duration of a render with [`RenderContext::run`], and read by
+/// the [`RenderHtml`](crate::view::RenderHtml) implementations that support each option. It is
+/// stored per-thread, so it applies to synchronous rendering and to the synchronous parts of a
+/// stream that are generated inside [`run`](RenderContext::run).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct RenderContext {
+    /// Whether to leave out closing tags that the HTML spec allows to be omitted.
+    pub omit_optional_tags: bool,
+    /// The maximum number of bytes of HTML to render, if any.
+    pub max_html_bytes: Option<usize>,
+    /// Whether images and iframes are lazy-loaded unless they set `loading` themselves.
+    pub default_lazy_loading: bool,
+    /// The locale of the document, as a BCP 47 language tag like `en-US`.
+    pub locale: Option<Cow<'static, str>>,
+    /// The nonce added to `<script>` and `<style>` elements, for a Content Security Policy.
+    pub nonce: Option<Arc<str>>,
+    /// The maximum number of suspense boundaries to stream out of order, if any.
+    pub max_out_of_order_boundaries: Option<usize>,
+    /// The spare capacity below which streams reserve space for a subtree before rendering it,
+    /// if any.
+    pub reserve_html_len_below: Option<usize>,
+    /// Whether to put nested elements on their own, indented lines.
+    pub pretty: bool,
+    /// Whether to give every element without a `data-testid` a generated one.
+    pub auto_testid: bool,
+    /// The function that is run over HTML given to `inner_html`, if any.
+    pub inner_html_sanitizer: Option<Sanitizer>,
+    /// Whether to open a `tracing` span for each out-of-order suspense boundary.
+    pub trace_boundaries: bool,
+    /// Whether to leave comments, including hydration markers, out of the rendered HTML.
+    pub strip_markers: bool,
+    /// Whether to wrap each [`FragmentMarker`](crate::view::fragment_marker::FragmentMarker) in
+    /// comments that carry its id.
+    pub fragment_markers: bool,
+    /// The number of bytes a stream buffers before ending a chunk and yielding, if any.
+    pub html_chunk_budget: Option<usize>,
+}
+
+/// The primary language subtags of languages that are written right-to-left.
+const RTL_LANGUAGES: &[&str] = &[
+    "ar", "arc", "ckb", "dv", "fa", "ha", "he", "iw", "ks", "ku", "ps", "sd",
+    "syr", "ug", "ur", "yi",
+];
+
+impl RenderContext {
+    /// Creates a render context with the default options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Leaves out closing tags like `</li>`, `</p>`, and `</td>` when the following sibling
+    /// (or the end of the parent) makes them implicit, as the HTML spec allows.
+    ///
+    /// The output parses to the same DOM tree in browsers, so it can still be hydrated.
+    pub fn omit_optional_tags(mut self, omit: bool) -> Self {
+        self.omit_optional_tags = omit;
+        self
+    }
+
+    /// Limits the output to the given number of bytes.
+    ///
+    /// This protects against runaway renders (like an accidentally infinite list) exhausting
+    /// memory. A stream stops appending HTML as soon as it goes past the limit, and ends without
+    /// sending any more chunks; use
+    /// [`StreamBuilder::limit_exceeded`](crate::ssr::StreamBuilder::limit_exceeded) to find out
+    /// whether that happened. [`to_html`](crate::view::RenderHtml::to_html) and its variants
+    /// stop rendering elements and list items once they are past the limit, and return an empty
+    /// string if it was exceeded; use [`try_run`](RenderContext::try_run) to get the error.
+    pub fn max_html_bytes(mut self, max: usize) -> Self {
+        self.max_html_bytes = Some(max);
+        self
+    }
+
+    /// Adds `loading="lazy"` to every `<img>` and `<iframe>` that doesn't set its own `loading`
+    /// attribute, so that an explicit `.loading("eager")` still takes precedence.
+    pub fn default_lazy_loading(mut self, lazy: bool) -> Self {
+        self.default_lazy_loading = lazy;
+        self
+    }
+
+    /// Sets the locale of the document.
+    ///
+    /// An `<html>` element that doesn't set its own `lang` or `dir` attributes gets
+    /// `lang="<locale>"`, and also `dir="rtl"` if the locale's language is written right-to-left.
+    pub fn locale(mut self, locale: impl Into<Cow<'static, str>>) -> Self {
+        self.locale = Some(locale.into());
+        self
+    }
+
+    /// Whether the [`locale`](RenderContext::locale) is for a language that is written
+    /// right-to-left, like Arabic or Hebrew.
+    pub fn is_rtl(&self) -> bool {
+        self.locale.as_deref().is_some_and(|locale| {
+            let language = locale.split(['-', '_']).next().unwrap_or_default();
+            RTL_LANGUAGES
+                .iter()
+                .any(|rtl| rtl.eq_ignore_ascii_case(language))
+        })
+    }
+
+    /// Sets the nonce for this render.
+    ///
+    /// Every `<script>` and `<style>` element that doesn't set its own `nonce` gets
+    /// `nonce="<nonce>"`, so that it is allowed by a Content Security Policy that includes the
+    /// same nonce. A new nonce should be generated for each response.
+    pub fn nonce(mut self, nonce: impl Into<Arc<str>>) -> Self {
+        self.nonce = Some(nonce.into());
+        self
+    }
+
+    /// Limits the number of suspense boundaries that are streamed as separate out-of-order
+    /// chunks.
+    ///
+    /// Each out-of-order boundary is sent as a fallback, followed later by the resolved HTML and
+    /// a script that swaps it in, which the client has to keep track of. Once the limit is
+    /// reached, any further boundaries are rendered in order instead: the stream waits for them
+    /// to resolve, and their HTML is sent inline. The limit applies to streams created inside
+    /// [`run`](RenderContext::run).
+    pub fn max_out_of_order_boundaries(mut self, max: usize) -> Self {
+        self.max_out_of_order_boundaries = Some(max);
+        self
+    }
+
+    /// Reserves space for each element before streaming it, using its
+    /// [`html_len`](crate::view::RenderHtml::html_len), when the stream's buffer has less than
+    /// `threshold` bytes of spare capacity.
+    ///
+    /// By default, subtrees that are streamed asynchronously are rendered into a buffer that
+    /// grows as needed, which can mean many reallocations for large subtrees. Computing
+    /// `html_len` walks the subtree an extra time, so it is skipped while the buffer still has
+    /// room: a large subtree reserves its full length up front, and the elements inside it then
+    /// fit without walking them again. This only changes how the buffer is allocated, not the
+    /// HTML that is rendered. The setting applies to streams created inside
+    /// [`run`](RenderContext::run).
+    pub fn reserve_html_len_below(mut self, threshold: usize) -> Self {
+        self.reserve_html_len_below = Some(threshold);
+        self
+    }
+
+    /// Indents the rendered HTML, putting nested elements on their own lines with two spaces per
+    /// level of nesting. This makes the output easier to read when debugging, or for static pages
+    /// that are meant to be read as source.
+    ///
+    /// Lines are only broken between elements, never next to text, so text content keeps its
+    /// whitespace. Nothing is changed inside `<pre>`, `<textarea>`, or elements like `<script>`
+    /// and `<style>` whose content is not HTML. Because whitespace is added between elements, the
+    /// output should not be used for hydration. This only applies to
+    /// [`to_html`](crate::view::RenderHtml::to_html) and its variants, not to streaming.
+    pub fn pretty(mut self, pretty: bool) -> Self {
+        self.pretty = pretty;
+        self
+    }
+
+    /// Gives every element that doesn't set its own `data-testid` a generated one, like
+    /// `data-testid="button-3"`, so that end-to-end tests can find elements without adding ids
+    /// to each of them by hand. An explicit [`testid`](crate::html::attribute::global::GlobalAttributes::testid)
+    /// is always kept.
+    ///
+    /// The generated id is the element's tag followed by its index among the elements rendered
+    /// inside [`run`](RenderContext::run), in document order. It only depends on the structure
+    /// of the view, so rendering the same view again gives the same ids. The ids are part of the
+    /// server-rendered HTML, and hydration keeps them, so they are the same on the client.
+    /// Elements that are only ever created on the client don't get one.
+    pub fn auto_testid(mut self, auto: bool) -> Self {
+        self.auto_testid = auto;
+        self
+    }
+
+    /// Runs the given function over the HTML set with
+    /// [`inner_html`](crate::html::element::inner_html) before it is rendered, like a sanitizer
+    /// that strips `<script>` elements from user content.
+    ///
+    /// This applies to HTML rendered on the server, both as a string and as a stream, and to
+    /// elements that are built or hydrated on the client inside [`run`](RenderContext::run).
+    /// An element keeps the sanitizer it was built with, so later reactive updates to its inner
+    /// HTML are sanitized too.
+    pub fn inner_html_sanitizer(
+        mut self,
+        sanitizer: fn(&str) -> String,
+    ) -> Self {
+        self.inner_html_sanitizer = Some(Sanitizer(sanitizer));
+        self
+    }
+
+    /// Opens a `tracing` span named `suspense_boundary` for each suspense boundary that is
+    /// streamed out of order, when the `tracing` feature is enabled.
+    ///
+    /// The span's `id` field is the boundary's id, as used in the `<template>` that carries its
+    /// HTML. Once the boundary resolves, the span records `duration_ms`, the time from the
+    /// boundary being reached to its HTML being ready, and `bytes`, the length of that HTML. This
+    /// makes it possible to find the slow or large parts of a streamed page. The setting applies
+    /// to streams created inside [`run`](RenderContext::run).
+    pub fn trace_boundaries(mut self, trace: bool) -> Self {
+        self.trace_boundaries = trace;
+        self
+    }
+
+    /// Leaves every comment out of the rendered HTML, including the markers that hydration and
+    /// [branch marking](crate::view::RenderHtml::to_html_branching) rely on, like `<!>` and
+    /// `<!--bo-...-->`.
+    ///
+    /// This makes statically generated pages that are never hydrated smaller. The markers are how
+    /// the client finds its way through the server-rendered HTML, so **a page rendered this way
+    /// can't be hydrated**, and neither can out-of-order streams, whose scripts look for
+    /// suspense markers, be used with it. The content of `<script>` and `<style>` elements, and
+    /// attribute values, are kept as they are. The setting applies to
+    /// [`to_html`](crate::view::RenderHtml::to_html) and its variants, and to streams created
+    /// inside [`run`](RenderContext::run).
+    pub fn strip_markers(mut self, strip: bool) -> Self {
+        self.strip_markers = strip;
+        self
+    }
+
+    /// Wraps the HTML of each [`FragmentMarker`](crate::view::fragment_marker::FragmentMarker)
+    /// in a pair of comments, `<!--$id-->` and `<!--/$id-->`, with the same id.
+    ///
+    /// This is for servers that update pages by sending the HTML of a single fragment, which the
+    /// client swaps in between the matching comments, rather than by hydrating them. The extra
+    /// comments are not expected by hydration, so **a page rendered this way can't be
+    /// hydrated**. The setting applies to [`to_html`](crate::view::RenderHtml::to_html) and its
+    /// variants, and to the synchronous parts of streams created inside
+    /// [`run`](RenderContext::run).
+    pub fn fragment_markers(mut self, markers: bool) -> Self {
+        self.fragment_markers = markers;
+        self
+    }
+
+    /// Ends the current chunk of a stream once more than `bytes` of synchronous HTML have been
+    /// buffered, deferring the rest of the list being rendered until the stream is polled again.
+    ///
+    /// A large tree with no suspense boundaries is otherwise serialized all at once, which holds
+    /// the task that polls the stream until all of it has been written out. With a budget, the
+    /// stream serializes and hands over bounded-size chunks, and lets other tasks run between
+    /// them. The HTML is the same either way. The setting applies to streams created inside
+    /// [`run`](RenderContext::run); see also
+    /// [`to_html_stream`](crate::view::RenderHtml::to_html_stream).
+    pub fn html_chunk_budget(mut self, bytes: usize) -> Self {
+        self.html_chunk_budget = Some(bytes);
+        self
+    }
+
+    /// Runs the function with this as the current render context, restoring the previous
+    /// context afterwards.
+    pub fn run<T>(self, fun: impl FnOnce() -> T) -> T {
+        self.run_with_counters(Arc::default(), fun)
+    }
+
+    fn run_with_counters<T>(
+        self,
+        counters: Arc<Counters>,
+        fun: impl FnOnce() -> T,
+    ) -> T {
+        struct Restore(Option<(RenderContext, Arc<Counters>, ElementOptions)>);
+
+        impl Drop for Restore {
+            fn drop(&mut self) {
+                if let Some((prev, prev_counters, prev_options)) = self.0.take()
+                {
+                    CURRENT.with(|cx| *cx.borrow_mut() = prev);
+                    COUNTERS.with(|counters| {
+                        *counters.borrow_mut() = prev_counters
+                    });
+                    ELEMENT_OPTIONS.set(prev_options);
+                }
+            }
+        }
+
+        let prev_options = ELEMENT_OPTIONS.replace(ElementOptions::of(&self));
+        let prev = CURRENT.with(|cx| cx.replace(self));
+        let prev_counters = COUNTERS.with(|prev| prev.replace(counters));
+        let _restore = Restore(Some((prev, prev_counters, prev_options)));
+        fun()
+    }
+
+    /// Runs the function with this as the current render context, like
+    /// [`run`](RenderContext::run), returning an error if the render exceeded
+    /// [`max_html_bytes`](RenderContext::max_html_bytes).
+    pub fn try_run<T>(
+        self,
+        fun: impl FnOnce() -> T,
+    ) -> Result<T, HtmlLimitExceeded> {
+        let prev = LIMIT_EXCEEDED.take();
+        let value = self.run(fun);
+        match LIMIT_EXCEEDED.replace(prev) {
+            Some(limit) => Err(HtmlLimitExceeded { limit }),
+            None => Ok(value),
+        }
+    }
+
+    /// Applies the function to the current render context.
+    pub fn with_current<T>(fun: impl FnOnce(&RenderContext) -> T) -> T {
+        CURRENT.with(|cx| fun(&cx.borrow()))
+    }
+}
+
+/// The render context of a render that is in progress, along with its id counters, so that part
+/// of the view can be rendered later (and possibly on another thread) as part of the same render.
+pub(crate) struct CapturedContext {
+    cx: RenderContext,
+    counters: Arc<Counters>,
+}
+
+impl CapturedContext {
+    /// Captures the current render context.
+    pub(crate) fn capture() -> Self {
+        Self {
+            cx: RenderContext::with_current(RenderContext::clone),
+            counters: COUNTERS.with_borrow(Arc::clone),
+        }
+    }
+
+    /// Runs the function in the captured render context, continuing its id counters.
+    pub(crate) fn run<T>(self, fun: impl FnOnce() -> T) -> T {
+        self.cx.run_with_counters(self.counters, fun)
+    }
+}
+
+/// Returns the index of the next element that gets an
+/// [`auto_testid`](RenderContext::auto_testid).
+pub(crate) fn next_auto_testid() -> usize {
+    COUNTERS
+        .with_borrow(|counters| counters.testid.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Returns the index of the next [`FragmentMarker`](crate::view::fragment_marker::FragmentMarker)
+/// that gets a generated id.
+pub(crate) fn next_fragment_id() -> usize {
+    COUNTERS.with_borrow(|counters| {
+        counters.fragment_id.fetch_add(1, Ordering::Relaxed)
+    })
+}
+
+/// Whether the HTML rendered into the buffer has gone past the current
+/// [`max_html_bytes`](RenderContext::max_html_bytes), after which nothing more is appended to it.
+pub(crate) fn html_limit_reached(buf: &str) -> bool {
+    ELEMENT_OPTIONS
+        .get()
+        .max_html_bytes
+        .is_some_and(|max| buf.len() > max)
+}
+
+/// Returns the HTML rendered to a string, or an empty string (recording that the limit was
+/// exceeded) if it is longer than the current [`max_html_bytes`](RenderContext::max_html_bytes),
+/// so that the HTML of a render that went past the limit is never used.
+pub(crate) fn check_html_limit(html: String) -> String {
+    match RenderContext::with_current(|cx| cx.max_html_bytes) {
+        Some(limit) if html.len() > limit => {
+            LIMIT_EXCEEDED.set(Some(limit));
+            String::new()
+        }
+        _ => html,
+    }
+}
+
+/// The error returned when rendered HTML grows beyond
+/// [`max_html_bytes`](RenderContext::max_html_bytes).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HtmlLimitExceeded {
+    /// The maximum number of bytes that was set.
+    pub limit: usize,
+}
+
+impl fmt::Display for HtmlLimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rendered HTML exceeded the limit of {} bytes",
+            self.limit
+        )
+    }
+}
+
+impl Error for HtmlLimitExceeded {}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{HtmlLimitExceeded, RenderContext};
+    use crate::{
+        html::{
+            attribute::global::GlobalAttributes,
+            element::{
+                body, html, img, li, script, style, table, td, tr, ul,
+                ElementChild,
+            },
+        },
+        view::{Position, RenderHtml},
+    };
+    use futures::{executor::block_on, StreamExt};
+
+    fn long_list() -> impl RenderHtml {
+        ul().child((0..1000).map(|n| li().child(n)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn render_stops_at_html_limit() {
+        let mut html = String::new();
+        let result = RenderContext::new()
+            .max_html_bytes(50)
+            .try_run(|| html = long_list().to_html());
+        assert_eq!(result, Err(HtmlLimitExceeded { limit: 50 }));
+        assert_eq!(html, "");
+    }
+
+    #[test]
+    fn render_within_html_limit_succeeds() {
+        let html = RenderContext::new()
+            .max_html_bytes(1_000_000)
+            .try_run(|| long_list().to_html());
+        assert!(html.is_ok());
+    }
+
+    #[test]
+    fn render_stops_growing_the_buffer_at_html_limit() {
+        let mut buf = String::new();
+        RenderContext::new().max_html_bytes(50).run(|| {
+            ul().child((0..100_000).map(|n| li().child(n)).collect::<Vec<_>>())
+                .to_html_with_buf(
+                    &mut buf,
+                    &mut Position::FirstChild,
+                    true,
+                    false,
+                    vec![],
+                )
+        });
+        assert!(buf.len() > 50 && buf.len() < 100, "{buf}");
+        assert!(buf.capacity() <= 128);
+    }
+
+    #[test]
+    fn stream_stops_appending_at_html_limit() {
+        let stream = RenderContext::new()
+            .max_html_bytes(50)
+            .run(|| long_list().to_html_stream_in_order());
+        assert!(stream.sync_buf.is_empty() && stream.chunks.is_empty());
+        assert!(stream.limit_exceeded().is_some());
+    }
+
+    #[test]
+    fn stream_ends_at_html_limit() {
+        let mut stream = RenderContext::new()
+            .max_html_bytes(50)
+            .run(|| long_list().to_html_stream_in_order());
+        let html = block_on((&mut stream).collect::<String>());
+        assert!(html.len() <= 50);
+        assert_eq!(
+            stream.limit_exceeded(),
+            Some(HtmlLimitExceeded { limit: 50 })
+        );
+        assert_eq!(
+            stream.limit_exceeded().unwrap().to_string(),
+            "rendered HTML exceeded the limit of 50 bytes"
+        );
+    }
+
+    #[test]
+    // the default attributes are added after the element's own, which sorting would move
+    #[cfg(not(feature = "canonical_attributes"))]
+    fn default_lazy_loading_respects_explicit_loading() {
+        use crate::html::element::iframe;
+
+        let html = RenderContext::new().default_lazy_loading(true).run(|| {
+            (
+                img().src("a.png"),
+                img().src("b.png").loading("eager"),
+                iframe().src("c.html"),
+            )
+                .to_html()
+        });
+        assert_eq!(
+            html,
+            "<img src=\"a.png\" loading=\"lazy\"><img src=\"b.png\" \
+             loading=\"eager\"><iframe src=\"c.html\" \
+             loading=\"lazy\"></iframe>"
+        );
+    }
+
+    #[test]
+    fn prefixed_attributes_are_not_mistaken_for_defaults() {
+        let html = RenderContext::new()
+            .default_lazy_loading(true)
+            .run(|| img().data_attr("loading", "eager").to_html());
+        assert_eq!(html, "<img data-loading=\"eager\" loading=\"lazy\">");
+    }
+
+    #[test]
+    fn no_lazy_loading_by_default() {
+        assert_eq!(img().src("a.png").to_html(), "<img src=\"a.png\">");
+    }
+
+    #[test]
+    // the default attributes are added after the element's own, which sorting would move
+    #[cfg(not(feature = "canonical_attributes"))]
+    fn rtl_locale_sets_dir() {
+        let html = RenderContext::new()
+            .locale("ar-EG")
+            .run(|| html().child(body()).to_html());
+        assert_eq!(
+            html,
+            "<html lang=\"ar-EG\" dir=\"rtl\"><body></body></html>"
+        );
+    }
+
+    #[test]
+    fn ltr_locale_only_sets_lang() {
+        let html = RenderContext::new()
+            .locale("fr")
+            .run(|| html().child(body()).to_html());
+        assert_eq!(html, "<html lang=\"fr\"><body></body></html>");
+    }
+
+    #[test]
+    fn explicit_lang_and_dir_take_precedence() {
+        let html = RenderContext::new()
+            .locale("he")
+            .run(|| html().dir("ltr").lang("en").to_html());
+        assert_eq!(html, "<html dir=\"ltr\" lang=\"en\"></html>");
+    }
+
+    #[test]
+    fn nonce_is_added_to_scripts_and_styles() {
+        let html = RenderContext::new().nonce("a\"b").run(|| {
+            (
+                script().child("run()"),
+                style().child("p {}"),
+                script().nonce("own"),
+                img(),
+            )
+                .to_html()
+        });
+        assert_eq!(
+            html,
+            "<script nonce=\"a&quot;b\">run()</script><style \
+             nonce=\"a&quot;b\">p {}</style><script \
+             nonce=\"own\"></script><img>"
+        );
+    }
+
+    #[test]
+    fn attributes_ending_in_the_same_name_are_not_mistaken_for_it() {
+        let html = RenderContext::new()
+            .nonce("n")
+            .run(|| script().data_attr("nonce", "x").to_html());
+        assert_eq!(html, r#"<script data-nonce="x" nonce="n"></script>"#);
+    }
+
+    #[test]
+    fn nonce_does_not_overcount_html_len() {
+        RenderContext::new().nonce("r4nd0m").run(|| {
+            let view = || script().child("run()");
+            assert!(view().html_len() <= view().to_html().len());
+
+            let view = || script().nonce("own").child("run()");
+            assert!(view().html_len() <= view().to_html().len());
+        });
+    }
+
+    #[test]
+    fn reserving_html_len_does_not_change_stream() {
+        let big_table = || {
+            table().child(
+                (0..10_000)
+                    .map(|n| tr().child((td().child(n), td().child("cell"))))
+                    .collect::<Vec<_>>(),
+            )
+        };
+        let render = |cx: RenderContext| {
+            let stream = cx.run(|| big_table().to_html_stream_in_order());
+            block_on(stream.collect::<String>())
+        };
+        assert_eq!(
+            render(RenderContext::new().reserve_html_len_below(1024)),
+            render(RenderContext::new())
+        );
+    }
+
+    #[test]
+    fn html_chunk_budget_splits_stream_without_changing_html() {
+        let tree =
+            || ul().child((0..2000).map(|n| li().child(n)).collect::<Vec<_>>());
+        let chunks = RenderContext::new()
+            .html_chunk_budget(1024)
+            .run(|| block_on(tree().to_html_stream().collect::<Vec<_>>()));
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|chunk| chunk.len() < 1024 + 64));
+        assert_eq!(chunks.concat(), tree().to_html());
+    }
+
+    #[cfg(feature = "reactive_graph")]
+    #[test]
+    fn to_html_stream_serializes_lazily() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        let rendered = Arc::new(AtomicUsize::new(0));
+        let items = (0..2000)
+            .map(|n| {
+                let rendered = Arc::clone(&rendered);
+                li().child(move || {
+                    rendered.fetch_add(1, Ordering::Relaxed);
+                    n
+                })
+            })
+            .collect::<Vec<_>>();
+        let mut stream = Box::pin(
+            RenderContext::new()
+                .html_chunk_budget(1024)
+                .run(|| ul().child(items).to_html_stream()),
+        );
+        let rendered = || rendered.load(Ordering::Relaxed);
+        assert!(rendered() < 200, "{} items rendered up front", rendered());
+
+        block_on(stream.next()).unwrap();
+        block_on(stream.next()).unwrap();
+        assert!(
+            rendered() < 400,
+            "{} items rendered for two chunks",
+            rendered()
+        );
+
+        block_on(stream.count());
+        assert_eq!(rendered(), 2000);
+    }
+
+    #[test]
+    fn deferred_list_items_keep_the_render_context() {
+        let cx = || {
+            RenderContext::new()
+                .auto_testid(true)
+                .html_chunk_budget(256)
+        };
+        let list =
+            || ul().child((0..100).map(|n| li().child(n)).collect::<Vec<_>>());
+        let streamed =
+            cx().run(|| block_on(list().to_html_stream().collect::<String>()));
+        assert_eq!(streamed, cx().run(|| list().to_html()));
+    }
+
+    #[test]
+    fn auto_testid_keeps_explicit_ids_and_is_stable() {
+        let render = || {
+            RenderContext::new().auto_testid(true).run(|| {
+                ul().child((li().testid("first").child("a"), li().child("b")))
+                    .to_html()
+            })
+        };
+        let html = render();
+        assert_eq!(
+            html,
+            "<ul data-testid=\"ul-0\"><li data-testid=\"first\">a</li><li \
+             data-testid=\"li-1\">b</li></ul>"
+        );
+        assert_eq!(render(), html);
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn trace_boundaries_records_id_and_bytes() {
+        use crate::{html::element::p, ssr::StreamBuilder, view::Position};
+        use std::sync::{Arc, Mutex};
+        use tracing::{
+            field::{Field, Visit},
+            span::{Attributes, Id, Record},
+            Event, Metadata, Subscriber,
+        };
+
+        /// Collects the fields of every span, as `(name, value)` pairs.
+        #[derive(Clone, Default)]
+        struct Fields(Arc<Mutex<Vec<(&'static str, String)>>>);
+
+        impl Visit for Fields {
+            fn record_debug(
+                &mut self,
+                field: &Field,
+                value: &dyn std::fmt::Debug,
+            ) {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push((field.name(), format!("{value:?}")));
+            }
+        }
+
+        impl Subscriber for Fields {
+            fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                self.0
+                    .lock()
+                    .unwrap()
+                    .push(("span", span.metadata().name().to_string()));
+                span.record(&mut self.clone());
+                Id::from_u64(1)
+            }
+
+            fn record(&self, _span: &Id, values: &Record<'_>) {
+                values.record(&mut self.clone());
+            }
+
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+            fn event(&self, _event: &Event<'_>) {}
+
+            fn enter(&self, _span: &Id) {}
+
+            fn exit(&self, _span: &Id) {}
+        }
+
+        let fields = Fields::default();
+        let html = tracing::subscriber::with_default(fields.clone(), || {
+            let stream =
+                RenderContext::new().trace_boundaries(true).run(|| {
+                    let mut stream = StreamBuilder::new(Some(vec![0]));
+                    stream.push_async_out_of_order(
+                        async { Some(p().child("resolved")) },
+                        &mut Position::FirstChild,
+                        false,
+                        vec![],
+                    );
+                    stream
+                });
+            block_on(stream.collect::<String>())
+        });
+        assert!(html.contains("<p>resolved</p>"));
+
+        let fields = fields.0.lock().unwrap();
+        let value = |name| {
+            fields
+                .iter()
+                .find(|(field, _)| *field == name)
+                .map(|(_, value)| value.as_str())
+        };
+        assert_eq!(value("span"), Some("suspense_boundary"));
+        assert_eq!(value("id"), Some("0-"));
+        assert!(value("duration_ms").is_some());
+        let bytes: usize = value("bytes").unwrap().parse().unwrap();
+        assert!(bytes >= "<p>resolved</p>".len());
+    }
+
+    #[cfg(feature = "ssr")]
+    #[test]
+    fn strip_markers_removes_branch_comments() {
+        use crate::view::any_view::IntoAny;
+
+        let view = || {
+            ul().child(vec![
+                li().child("a").into_any(),
+                li().child(("b", "c")).into_any(),
+            ])
+        };
+        assert!(view().to_html_branching().contains("<!--bo-"));
+
+        let html = RenderContext::new()
+            .strip_markers(true)
+            .run(|| view().to_html_branching());
+        assert_eq!(html, "<ul><li>a</li><li>bc</li></ul>");
+
+        let stream = RenderContext::new()
+            .strip_markers(true)
+            .run(|| view().to_html_stream_in_order_branching());
+        assert_eq!(block_on(stream.collect::<String>()), html);
+    }
+}
@@ -2,17 +2,34 @@ use crate::{
     html::attribute::any_attribute::AnyAttribute,
     view::{Position, RenderHtml},
 };
-use futures::Stream;
+use futures::{FutureExt, Stream, StreamExt};
 use std::{
     collections::VecDeque,
     fmt::{Debug, Write},
     future::Future,
     mem,
+    panic::AssertUnwindSafe,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
     task::{Context, Poll},
 };
 
+mod assets;
+mod context;
+mod pool;
+mod strip_markers;
+pub use assets::AssetRef;
+pub(crate) use assets::{
+    collect_assets, collecting_assets, is_asset_attribute, record_asset,
+};
+pub use context::*;
+pub(crate) use pool::{recycle_buffer, take_buffer};
+pub(crate) use strip_markers::strip_if_enabled;
+use strip_markers::StripMarkers;
+
 /// Manages streaming HTML rendering for the response to a single request.
 #[derive(Default)]
 pub struct StreamBuilder {
@@ -21,6 +38,16 @@ pub struct StreamBuilder {
     pending: Option<ChunkFuture>,
     pending_ooo: VecDeque<PinnedFuture<OooChunk>>,
     id: Option<Vec<u16>>,
+    max_html_bytes: Option<usize>,
+    emitted: usize,
+    chunks_emitted: usize,
+    limit_exceeded: bool,
+    ooo_boundaries_left: Option<Arc<AtomicUsize>>,
+    reserve_html_len_below: Option<usize>,
+    #[cfg(feature = "tracing")]
+    trace_boundaries: bool,
+    strip_markers: Option<StripMarkers>,
+    html_chunk_budget: Option<usize>,
 }
 
 type PinnedFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
@@ -34,13 +61,95 @@ impl StreamBuilder {
 
     /// Creates a new stream with a given capacity in the synchronous buffer and an identifier.
     pub fn with_capacity(capacity: usize, id: Option<Vec<u16>>) -> Self {
+        RenderContext::with_current(|cx| Self {
+            id,
+            sync_buf: take_buffer(capacity),
+            max_html_bytes: cx.max_html_bytes,
+            ooo_boundaries_left: cx
+                .max_out_of_order_boundaries
+                .map(|max| Arc::new(AtomicUsize::new(max))),
+            reserve_html_len_below: cx.reserve_html_len_below,
+            #[cfg(feature = "tracing")]
+            trace_boundaries: cx.trace_boundaries,
+            strip_markers: cx.strip_markers.then(StripMarkers::default),
+            html_chunk_budget: cx.html_chunk_budget,
+            ..Default::default()
+        })
+    }
+
+    /// Creates a stream for rendering part of this one separately, which shares its budget of
+    /// [`max_out_of_order_boundaries`](RenderContext::max_out_of_order_boundaries) and its
+    /// [`reserve_html_len_below`](RenderContext::reserve_html_len_below),
+    /// [`trace_boundaries`](RenderContext::trace_boundaries), and
+    /// [`html_chunk_budget`](RenderContext::html_chunk_budget) settings.
+    pub fn sub_builder(&self, id: Option<Vec<u16>>) -> Self {
         Self {
             id,
-            sync_buf: String::with_capacity(capacity),
+            ooo_boundaries_left: self.ooo_boundaries_left.clone(),
+            reserve_html_len_below: self.reserve_html_len_below,
+            html_chunk_budget: self.html_chunk_budget,
+            #[cfg(feature = "tracing")]
+            trace_boundaries: self.trace_boundaries,
             ..Default::default()
         }
     }
 
+    /// Reserves space for the view in the synchronous buffer, if the render context
+    /// [asks for it](RenderContext::reserve_html_len_below) and the buffer is running out of
+    /// spare capacity.
+    pub fn reserve_html_len<View>(&mut self, view: &View)
+    where
+        View: RenderHtml,
+    {
+        if let Some(threshold) = self.reserve_html_len_below {
+            let spare = self.sync_buf.capacity() - self.sync_buf.len();
+            if spare < threshold {
+                self.sync_buf.reserve(view.html_len());
+            }
+        }
+    }
+
+    /// Claims one of the out-of-order boundaries allowed by
+    /// [`max_out_of_order_boundaries`](RenderContext::max_out_of_order_boundaries).
+    ///
+    /// Returns `false` if none are left, in which case the boundary should be rendered in order
+    /// instead of being streamed as a separate chunk.
+    pub fn claim_out_of_order_boundary(&self) -> bool {
+        self.ooo_boundaries_left.as_ref().is_none_or(|left| {
+            left.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |left| {
+                left.checked_sub(1)
+            })
+            .is_ok()
+        })
+    }
+
+    /// Returns an error if the stream was ended early because it exceeded the
+    /// [`max_html_bytes`](RenderContext::max_html_bytes) of the render context it was created
+    /// in.
+    pub fn limit_exceeded(&self) -> Option<HtmlLimitExceeded> {
+        self.max_html_bytes
+            .filter(|_| self.limit_exceeded)
+            .map(|limit| HtmlLimitExceeded { limit })
+    }
+
+    /// Returns the number of chunks of HTML the stream has produced so far.
+    ///
+    /// Each item yielded by the stream is one chunk, so this tells how the HTML was split up
+    /// as it was sent, which is useful in tests and for metrics.
+    pub fn chunk_count(&self) -> usize {
+        self.chunks_emitted
+    }
+
+    /// Completes the stream and waits for all of it, returning each chunk separately rather
+    /// than as one string.
+    ///
+    /// The chunks are the same, and in the same order, as the items of the stream: out-of-order
+    /// chunks are resolved into the `<template>` and script that move them into place, exactly
+    /// as they would be sent to the browser.
+    pub async fn finish_with_chunks(self) -> Vec<String> {
+        self.finish().collect().await
+    }
+
     /// Reserves additional space in the synchronous buffer.
     pub fn reserve(&mut self, additional: usize) {
         self.sync_buf.reserve(additional);
@@ -48,7 +157,65 @@ impl StreamBuilder {
 
     /// Pushes text into the synchronous buffer.
     pub fn push_sync(&mut self, string: &str) {
-        self.sync_buf.push_str(string);
+        if !self.limit_exceeded {
+            self.sync_buf.push_str(string);
+            self.check_html_limit();
+        }
+    }
+
+    /// Ends the stream if the HTML it has sent and buffered so far is longer than its
+    /// [`max_html_bytes`](RenderContext::max_html_bytes), dropping whatever is buffered and
+    /// ignoring anything that is pushed afterwards.
+    fn check_html_limit(&mut self) {
+        if self
+            .max_html_bytes
+            .is_some_and(|max| self.emitted + self.sync_buf.len() > max)
+        {
+            self.limit_exceeded = true;
+            self.sync_buf.clear();
+            self.chunks.clear();
+        }
+    }
+
+    /// Whether the synchronous buffer has grown past the
+    /// [`html_chunk_budget`](RenderContext::html_chunk_budget), so that whatever is left of the
+    /// view being rendered should be [deferred](StreamBuilder::push_deferred).
+    pub fn over_chunk_budget(&self) -> bool {
+        self.html_chunk_budget
+            .is_some_and(|budget| self.sync_buf.len() >= budget)
+    }
+
+    /// Ends the current chunk, and renders the view only once the stream has sent it and been
+    /// polled again, yielding to the executor in between.
+    ///
+    /// The view is rendered in the render context that is current now, so the HTML is the same as
+    /// if it had been rendered immediately.
+    pub fn push_deferred<const OUT_OF_ORDER: bool, View>(
+        &mut self,
+        view: View,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) where
+        View: RenderHtml + 'static,
+    {
+        let mut builder = self.sub_builder(self.clone_id());
+        let cx = CapturedContext::capture();
+        let mut position = *position;
+        self.push_async(async move {
+            YieldOnce(false).await;
+            cx.run(|| {
+                view.to_html_async_with_buf::<OUT_OF_ORDER>(
+                    &mut builder,
+                    &mut position,
+                    escape,
+                    mark_branches,
+                    extra_attrs,
+                )
+            });
+            builder.finish().take_chunks()
+        });
     }
 
     /// Pushes an async block into the stream.
@@ -56,6 +223,9 @@ impl StreamBuilder {
         &mut self,
         fut: impl Future<Output = VecDeque<StreamChunk>> + Send + 'static,
     ) {
+        if self.limit_exceeded {
+            return;
+        }
         // flush sync chunk
         let sync = mem::take(&mut self.sync_buf);
         if !sync.is_empty() {
@@ -66,9 +236,18 @@ impl StreamBuilder {
         });
     }
 
+    /// Ends the current chunk, so that the HTML in the synchronous buffer is sent as soon as the
+    /// stream reaches it, rather than being combined with the HTML that follows.
+    pub fn flush_chunk(&mut self) {
+        self.push_async(async { VecDeque::new() });
+    }
+
     /// Mutates the synchronous buffer.
     pub fn with_buf(&mut self, fun: impl FnOnce(&mut String)) {
-        fun(&mut self.sync_buf)
+        if !self.limit_exceeded {
+            fun(&mut self.sync_buf);
+            self.check_html_limit();
+        }
     }
 
     /// Takes all chunks currently available in the stream, including the synchronous buffer.
@@ -183,6 +362,9 @@ impl StreamBuilder {
     }
 
     /// Injects an out-of-order chunk into the stream, using the given nonce for `<script>` tags.
+    ///
+    /// If resolving or rendering the view panics, the chunk only removes the suspense markers,
+    /// leaving the fallback in place, and the rest of the stream is still sent.
     pub fn push_async_out_of_order_with_nonce<View>(
         &mut self,
         view: impl Future<Output = Option<View>> + Send + 'static,
@@ -193,56 +375,125 @@ impl StreamBuilder {
     ) where
         View: RenderHtml,
     {
-        let id = self.clone_id();
+        let subbuilder = self.sub_builder(self.clone_id());
         // copy so it's not updated by additional iterations
         // i.e., restart in the same position we were at when we suspended
         let mut position = *position;
+        let mut id = String::new();
+        if let Some(ids) = &subbuilder.id {
+            for piece in ids {
+                write!(&mut id, "{piece}-").unwrap();
+            }
+        }
+        #[cfg(feature = "tracing")]
+        let (span, started) = if self.trace_boundaries {
+            let span = tracing::info_span!(
+                "suspense_boundary",
+                id = %id,
+                duration_ms = tracing::field::Empty,
+                bytes = tracing::field::Empty,
+            );
+            (span, Some(std::time::Instant::now()))
+        } else {
+            (tracing::Span::none(), None)
+        };
 
-        self.chunks.push_back(StreamChunk::OutOfOrder {
-            chunks: Box::pin(async move {
-                let view = view.await;
-
-                let mut subbuilder = StreamBuilder::new(id);
-                let mut id = String::new();
-                if let Some(ids) = &subbuilder.id {
-                    for piece in ids {
-                        write!(&mut id, "{piece}-").unwrap();
-                    }
-                }
-                if let Some(id) = subbuilder.id.as_mut() {
-                    id.push(0);
+        // if the boundary panics while it is being resolved or rendered, the fallback that was
+        // already sent stays in place, and the rest of the stream goes on
+        let fallback = OooChunk {
+            id: id.clone(),
+            chunks: VecDeque::new(),
+            replace: false,
+            nonce: nonce.clone(),
+        };
+
+        #[cfg(feature = "tracing")]
+        let boundary_span = span.clone();
+        let chunks = async move {
+            let view = view.await;
+
+            let mut subbuilder = subbuilder;
+            if let Some(id) = subbuilder.id.as_mut() {
+                id.push(0);
+            }
+            let replace = view.is_some();
+            view.to_html_async_with_buf::<true>(
+                &mut subbuilder,
+                &mut position,
+                true,
+                mark_branches,
+                extra_attrs,
+            );
+            let chunks = subbuilder.finish().take_chunks();
+            let mut flattened_chunks = VecDeque::with_capacity(chunks.len());
+            for chunk in chunks {
+                // this will wait for any ErrorBoundary async nodes and flatten them out
+                if let StreamChunk::Async { chunks } = chunk {
+                    flattened_chunks.extend(chunks.await);
+                } else {
+                    flattened_chunks.push_back(chunk);
                 }
-                let replace = view.is_some();
-                view.to_html_async_with_buf::<true>(
-                    &mut subbuilder,
-                    &mut position,
-                    true,
-                    mark_branches,
-                    extra_attrs,
+            }
+
+            #[cfg(feature = "tracing")]
+            if let Some(started) = started {
+                let span = boundary_span;
+                span.record(
+                    "duration_ms",
+                    started.elapsed().as_secs_f64() * 1000.0,
                 );
-                let chunks = subbuilder.finish().take_chunks();
-                let mut flattened_chunks =
-                    VecDeque::with_capacity(chunks.len());
-                for chunk in chunks {
-                    // this will wait for any ErrorBoundary async nodes and flatten them out
-                    if let StreamChunk::Async { chunks } = chunk {
-                        flattened_chunks.extend(chunks.await);
-                    } else {
-                        flattened_chunks.push_back(chunk);
-                    }
-                }
+                span.record(
+                    "bytes",
+                    flattened_chunks
+                        .iter()
+                        .map(|chunk| match chunk {
+                            StreamChunk::Sync(html) => html.len(),
+                            _ => 0,
+                        })
+                        .sum::<usize>(),
+                );
+            }
 
-                OooChunk {
-                    id,
-                    chunks: flattened_chunks,
-                    replace,
-                    nonce,
-                }
-            }),
+            OooChunk {
+                id,
+                chunks: flattened_chunks,
+                replace,
+                nonce,
+            }
+        };
+        let chunks = AssertUnwindSafe(chunks)
+            .catch_unwind()
+            .map(|chunk| chunk.unwrap_or(fallback));
+        #[cfg(feature = "tracing")]
+        let chunks = tracing::Instrument::instrument(chunks, span);
+
+        self.chunks.push_back(StreamChunk::OutOfOrder {
+            chunks: Box::pin(chunks),
         });
     }
 }
 
+/// An empty chunk that is pending the first time it is polled, so that the task polling the
+/// stream gives other tasks a chance to run.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = VecDeque<StreamChunk>;
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Self::Output> {
+        if self.0 {
+            Poll::Ready(VecDeque::new())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
+
 impl Debug for StreamBuilder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("StreamBuilderInner")
@@ -352,13 +603,11 @@ impl Debug for StreamChunk {
     }
 }
 
-impl Stream for StreamBuilder {
-    type Item = String;
-
-    fn poll_next(
+impl StreamBuilder {
+    fn poll_next_chunk(
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
-    ) -> Poll<Option<Self::Item>> {
+    ) -> Poll<Option<String>> {
         let mut this = self.as_mut();
         let pending = this.pending.take();
         if let Some(mut pending) = pending {
@@ -371,7 +620,7 @@ impl Stream for StreamBuilder {
                     for chunk in chunks.into_iter().rev() {
                         this.chunks.push_front(chunk);
                     }
-                    self.poll_next(cx)
+                    self.poll_next_chunk(cx)
                 }
             }
         } else {
@@ -467,6 +716,7 @@ impl Stream for StreamBuilder {
                 }
                 Some(StreamChunk::Sync(value)) => {
                     this.sync_buf.push_str(&value);
+                    recycle_buffer(value);
                     loop {
                         match this.chunks.pop_front() {
                             None => break,
@@ -483,16 +733,17 @@ impl Stream for StreamBuilder {
                             }
                             Some(StreamChunk::Sync(next)) => {
                                 this.sync_buf.push_str(&next);
+                                recycle_buffer(next);
                             }
                         }
                     }
 
-                    this.poll_next(cx)
+                    this.poll_next_chunk(cx)
                 }
                 Some(StreamChunk::Async { chunks, .. }) => {
                     this.pending = Some(chunks);
                     if this.sync_buf.is_empty() {
-                        self.poll_next(cx)
+                        self.poll_next_chunk(cx)
                     } else {
                         Poll::Ready(Some(mem::take(&mut this.sync_buf)))
                     }
@@ -500,7 +751,7 @@ impl Stream for StreamBuilder {
                 Some(StreamChunk::OutOfOrder { chunks, .. }) => {
                     this.pending_ooo.push_back(chunks);
                     if this.sync_buf.is_empty() {
-                        self.poll_next(cx)
+                        self.poll_next_chunk(cx)
                     } else {
                         Poll::Ready(Some(mem::take(&mut this.sync_buf)))
                     }
@@ -510,6 +761,118 @@ impl Stream for StreamBuilder {
     }
 }
 
+impl Stream for StreamBuilder {
+    type Item = String;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if self.limit_exceeded {
+            return Poll::Ready(None);
+        }
+        let mut chunk = self.as_mut().poll_next_chunk(cx);
+        if let (Poll::Ready(html), Some(strip)) =
+            (&mut chunk, &mut self.strip_markers)
+        {
+            match html {
+                Some(html) => *html = strip.strip(html),
+                None => *html = strip.finish(),
+            }
+        }
+        if let Poll::Ready(Some(chunk)) = &chunk {
+            self.emitted += chunk.len();
+            if self.max_html_bytes.is_some_and(|max| self.emitted > max) {
+                self.limit_exceeded = true;
+                return Poll::Ready(None);
+            }
+            self.chunks_emitted += 1;
+        }
+        chunk
+    }
+}
+
+#[cfg(all(test, feature = "ssr", feature = "reactive_graph"))]
+mod chunk_tests {
+    use crate::{
+        html::element::{main, p, ElementChild},
+        reactive_graph::Suspend,
+        view::RenderHtml,
+    };
+    use futures::StreamExt;
+    use reactive_graph::owner::Owner;
+
+    // `Suspend` polls its future once while the shell is rendered, and a boundary that is ready
+    // when the stream first polls it is inlined into the shell, so this has to stay pending for
+    // two polls to end up in a chunk of its own
+    async fn load<T>(value: T) -> T {
+        tokio::task::yield_now().await;
+        tokio::task::yield_now().await;
+        value
+    }
+
+    fn view() -> impl RenderHtml {
+        main().child((
+            p().child("before"),
+            Suspend::new(async { p().child(load("loaded").await) }),
+            p().child("after"),
+        ))
+    }
+
+    #[tokio::test]
+    async fn suspended_section_is_sent_as_a_later_chunk() {
+        let owner = Owner::new();
+        let stream = owner.with(|| view().to_html_stream_out_of_order());
+        let chunks = stream.finish_with_chunks().await;
+
+        assert_eq!(chunks.len(), 2, "{chunks:?}");
+        assert!(chunks[0].starts_with("<main><p>before</p><!--s-1-o-->"));
+        assert!(chunks[0].ends_with("<p>after</p></main>"));
+        assert!(chunks[1].starts_with(r#"<template id="1-f"><p>loaded</p>"#));
+    }
+
+    #[tokio::test]
+    async fn panicking_boundary_does_not_end_the_stream() {
+        let owner = Owner::new();
+        let stream = owner.with(|| {
+            main()
+                .child((
+                    Suspend::new(async {
+                        let text = load(None::<&str>).await;
+                        p().child(text.expect("failed to load"))
+                    }),
+                    Suspend::new(async { p().child(load("loaded").await) }),
+                ))
+                .to_html_stream_out_of_order()
+        });
+        let html = stream.finish_with_chunks().await.concat();
+
+        // the panicking boundary is sent empty, and only the other one replaces its fallback
+        assert!(html.contains(r#"<template id="1-f"></template>"#), "{html}");
+        assert_eq!(html.matches("range.deleteContents()").count(), 1);
+        assert!(
+            html.contains(r#"<template id="2-f"><p>loaded</p>"#),
+            "{html}"
+        );
+        assert!(html.ends_with("</script>"), "{html}");
+    }
+
+    #[tokio::test]
+    async fn chunk_count_follows_the_stream() {
+        let owner = Owner::new();
+        let mut stream = owner.with(|| view().to_html_stream_in_order());
+        assert_eq!(stream.chunk_count(), 0);
+
+        let first = stream.next().await.unwrap();
+        assert_eq!(first, "<main><p>before</p>");
+        assert_eq!(stream.chunk_count(), 1);
+
+        let rest = stream.by_ref().collect::<Vec<_>>().await;
+        assert!(rest.concat().contains("<p>loaded</p>"));
+        assert_eq!(stream.chunk_count(), 1 + rest.len());
+    }
+}
+
 /*
 #[cfg(test)]
 mod tests {
@@ -0,0 +1,83 @@
+//! A per-thread pool of `String` buffers that are reused between renders, with the
+//! `buffer_pool` feature.
+//!
+//! Without the feature, buffers are allocated and freed as usual.
+
+#[cfg(feature = "buffer_pool")]
+use std::cell::RefCell;
+
+/// The most buffers kept in each thread's pool.
+#[cfg(feature = "buffer_pool")]
+const MAX_POOLED_BUFFERS: usize = 64;
+
+/// Buffers that have grown larger than this are freed rather than kept, so that one very large
+/// page doesn't pin its memory for the life of the thread.
+#[cfg(feature = "buffer_pool")]
+const MAX_POOLED_CAPACITY: usize = 64 * 1024;
+
+#[cfg(feature = "buffer_pool")]
+thread_local! {
+    static POOL: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns an empty buffer with at least the given capacity, reusing a pooled one if there is
+/// one.
+pub(crate) fn take_buffer(capacity: usize) -> String {
+    #[cfg(feature = "buffer_pool")]
+    if let Some(mut buf) = POOL.with_borrow_mut(Vec::pop) {
+        buf.reserve(capacity);
+        return buf;
+    }
+    String::with_capacity(capacity)
+}
+
+/// Gives a buffer back to the pool, once its contents are no longer needed.
+///
+/// The buffer is cleared before it is pooled, so nothing written into it can be seen by
+/// whoever takes it next.
+pub(crate) fn recycle_buffer(buf: String) {
+    #[cfg(feature = "buffer_pool")]
+    if buf.capacity() > 0 && buf.capacity() <= MAX_POOLED_CAPACITY {
+        let mut buf = buf;
+        buf.clear();
+        POOL.with_borrow_mut(|pool| {
+            if pool.len() < MAX_POOLED_BUFFERS {
+                pool.push(buf);
+            }
+        });
+    }
+    #[cfg(not(feature = "buffer_pool"))]
+    drop(buf);
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{recycle_buffer, take_buffer};
+    use crate::{
+        html::element::{li, p, ul, ElementChild},
+        view::RenderHtml,
+    };
+    use futures::{executor::block_on, StreamExt};
+
+    #[test]
+    fn recycled_buffers_are_empty() {
+        let mut secret = take_buffer(0);
+        secret.push_str("session=abc123");
+        recycle_buffer(secret);
+
+        let buf = take_buffer(4);
+        assert!(buf.is_empty());
+        assert!(buf.capacity() >= 4);
+    }
+
+    #[test]
+    fn no_data_bleeds_into_the_next_render() {
+        let first = ul()
+            .child((0..100).map(|n| li().child(n)).collect::<Vec<_>>())
+            .to_html_stream_in_order();
+        block_on(first.collect::<String>());
+
+        let second = p().child("fresh").to_html_stream_in_order();
+        assert_eq!(block_on(second.collect::<String>()), "<p>fresh</p>");
+    }
+}
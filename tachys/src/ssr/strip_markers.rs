@@ -0,0 +1,196 @@
+//! Removes comments from rendered HTML, for
+//! [`RenderContext::strip_markers`](crate::ssr::RenderContext::strip_markers).
+//!
+//! Text content never contains a raw `<`, because it is escaped, so any `<!` outside of a tag
+//! starts a comment. Tags are copied as they are, respecting quoted attribute values, and so is
+//! the content of `<script>` and `<style>`, which is not HTML.
+
+use super::RenderContext;
+
+/// Removes the comments from HTML rendered to a string, if the current render context
+/// [asks for it](RenderContext::strip_markers).
+pub(crate) fn strip_if_enabled(html: String) -> String {
+    if RenderContext::with_current(|cx| cx.strip_markers) {
+        let mut strip = StripMarkers::default();
+        let mut out = strip.strip(&html);
+        out.extend(strip.finish());
+        out
+    } else {
+        html
+    }
+}
+
+/// The state of a scan that can continue in the next chunk of a stream.
+#[derive(Debug, Default)]
+pub(crate) struct StripMarkers {
+    /// The closing tag that ends the raw text element the last chunk ended in, if any.
+    raw_text_end: Option<&'static str>,
+    /// Whether the last chunk ended inside a comment.
+    in_comment: bool,
+    /// The end of the last chunk, held back because it may be the start of a comment, a tag, or
+    /// the end of a comment or raw text element, that continues in the next chunk.
+    held_back: String,
+}
+
+impl StripMarkers {
+    /// Returns the HTML with all comments, including `<!>` placeholders, removed.
+    ///
+    /// Anything that can't be decided until the next chunk is held back, and returned at the
+    /// start of the next call, or by [`finish`](Self::finish).
+    pub(crate) fn strip(&mut self, html: &str) -> String {
+        let joined;
+        let mut rest = if self.held_back.is_empty() {
+            html
+        } else {
+            joined = std::mem::take(&mut self.held_back) + html;
+            joined.as_str()
+        };
+        let mut out = String::with_capacity(rest.len());
+        loop {
+            if self.in_comment {
+                match rest.find("-->") {
+                    Some(len) => {
+                        rest = &rest[len + 3..];
+                        self.in_comment = false;
+                    }
+                    None => {
+                        let kept = partial_end_len(rest, "-->");
+                        self.held_back.push_str(&rest[rest.len() - kept..]);
+                        return out;
+                    }
+                }
+            }
+
+            if let Some(end) = self.raw_text_end {
+                match rest.find(end) {
+                    Some(len) => {
+                        out.push_str(&rest[..len]);
+                        rest = &rest[len..];
+                        self.raw_text_end = None;
+                    }
+                    None => {
+                        let kept = partial_end_len(rest, end);
+                        out.push_str(&rest[..rest.len() - kept]);
+                        self.held_back.push_str(&rest[rest.len() - kept..]);
+                        return out;
+                    }
+                }
+            }
+
+            let Some(start) = rest.find('<') else {
+                out.push_str(rest);
+                return out;
+            };
+            out.push_str(&rest[..start]);
+            rest = &rest[start..];
+
+            if let Some(after) = rest.strip_prefix("<!>") {
+                rest = after;
+            } else if let Some(comment) = rest.strip_prefix("<!--") {
+                rest = comment;
+                self.in_comment = true;
+            } else if "<!--".starts_with(rest) {
+                // `<`, `<!` or `<!-` at the end of the chunk
+                self.held_back.push_str(rest);
+                return out;
+            } else if let Some(len) = tag_len(rest) {
+                let tag = &rest[..len];
+                out.push_str(tag);
+                rest = &rest[len..];
+                self.raw_text_end = raw_text_end(tag);
+            } else {
+                self.held_back.push_str(rest);
+                return out;
+            }
+        }
+    }
+
+    /// Returns whatever was held back at the end of the last chunk, once there are no more
+    /// chunks. An unterminated comment is dropped.
+    pub(crate) fn finish(&mut self) -> Option<String> {
+        let held_back = std::mem::take(&mut self.held_back);
+        (!self.in_comment && !held_back.is_empty()).then_some(held_back)
+    }
+}
+
+/// The length of the tag at the start of `html`, up to and including its closing `>`, or `None`
+/// if it doesn't end in `html`.
+fn tag_len(html: &str) -> Option<usize> {
+    let mut quote = None;
+    for (idx, ch) in html.char_indices().skip(1) {
+        match (quote, ch) {
+            (None, '"' | '\'') => quote = Some(ch),
+            (Some(open), _) if ch == open => quote = None,
+            (None, '>') => return Some(idx + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// The length of the longest end of `text` that `end` starts with, which may be completed by the
+/// next chunk.
+fn partial_end_len(text: &str, end: &str) -> usize {
+    (1..end.len())
+        .rev()
+        .find(|&len| text.ends_with(&end[..len]))
+        .unwrap_or(0)
+}
+
+/// The closing tag of the raw text element opened by `tag`, if it opens one.
+fn raw_text_end(tag: &str) -> Option<&'static str> {
+    let name = tag[1..]
+        .split(|c: char| c.is_ascii_whitespace() || c == '>' || c == '/')
+        .next()?;
+    match name {
+        "script" => Some("</script"),
+        "style" => Some("</style"),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StripMarkers;
+
+    #[test]
+    fn removes_comments_outside_of_tags_and_raw_text() {
+        let html = r#"<!DOCTYPE html><p title="<!--x-->">a<!>b<!--bo-1--></p><script>let c = "<!--";</script><!--bc-1-->"#;
+        assert_eq!(
+            StripMarkers::default().strip(html),
+            r#"<!DOCTYPE html><p title="<!--x-->">ab</p><script>let c = "<!--";</script>"#
+        );
+    }
+
+    #[test]
+    fn raw_text_continues_across_chunks() {
+        let mut strip = StripMarkers::default();
+        assert_eq!(strip.strip("<style>a<!>"), "<style>a<!>");
+        assert_eq!(strip.strip("b</style><!>"), "b</style>");
+    }
+
+    #[test]
+    fn markers_split_across_chunks_are_removed() {
+        let mut strip = StripMarkers::default();
+        assert_eq!(strip.strip("<p>a<!"), "<p>a");
+        assert_eq!(strip.strip("-- bo-1 -"), "");
+        assert_eq!(strip.strip("->b<!"), "b");
+        assert_eq!(strip.strip("></p>"), "</p>");
+        assert_eq!(strip.finish(), None);
+    }
+
+    #[test]
+    fn raw_text_end_split_across_chunks_is_found() {
+        let mut strip = StripMarkers::default();
+        assert_eq!(strip.strip("<script>a</scr"), "<script>a");
+        assert_eq!(strip.strip("ipt><!>b"), "</script>b");
+    }
+
+    #[test]
+    fn held_back_text_is_returned_when_finished() {
+        let mut strip = StripMarkers::default();
+        assert_eq!(strip.strip("<p>a</p><"), "<p>a</p>");
+        assert_eq!(strip.finish().as_deref(), Some("<"));
+        assert_eq!(strip.finish(), None);
+    }
+}
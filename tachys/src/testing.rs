@@ -0,0 +1,122 @@
+use crate::{dom::document, view::RenderHtml};
+
+/// Renders the view to HTML, creates DOM nodes from that HTML, and hydrates them.
+///
+/// This is a one-call round-trip test for hydration: it panics if hydration fails (for example,
+/// because the view walks the DOM differently from how it rendered it), or if hydrating the
+/// server-rendered HTML changes the DOM in any way.
+///
+/// ## Panics
+/// Panics if called outside a browser environment, if hydration panics, or if the hydrated
+/// DOM does not match the HTML that was rendered.
+#[track_caller]
+pub fn assert_hydrates<V>(view: V)
+where
+    V: RenderHtml + Clone,
+{
+    let html = view.clone().to_html();
+
+    // the browser normalizes some of our output while parsing (e.g., `<!>` becomes `<!---->`),
+    // so we compare against an untouched copy parsed from the same HTML
+    let expected = document().create_element("div").unwrap();
+    expected.set_inner_html(&html);
+
+    let container = document().create_element("div").unwrap();
+    container.set_inner_html(&html);
+
+    let state = view.hydrate_from::<true>(&container);
+    assert_eq!(
+        container.inner_html(),
+        expected.inner_html(),
+        "hydrating the server-rendered HTML changed the DOM"
+    );
+    drop(state);
+}
+
+/// Checks that a view's [`MIN_LENGTH`](RenderHtml::MIN_LENGTH) and
+/// [`html_len`](RenderHtml::html_len) are consistent with the HTML it renders.
+///
+/// Both are used to size buffers before rendering, so `MIN_LENGTH` has to be a lower bound on
+/// `html_len`, which in turn should not be more than the length of the rendered HTML.
+///
+/// ## Panics
+/// Panics if `MIN_LENGTH` is more than `html_len`, or if `html_len` is more than the length of
+/// the rendered HTML.
+#[track_caller]
+pub fn assert_min_length_valid<V>(view: V)
+where
+    V: RenderHtml,
+{
+    let html_len = view.html_len();
+    let rendered_len = view.to_html().len();
+    assert!(
+        V::MIN_LENGTH <= html_len,
+        "MIN_LENGTH ({}) is more than html_len ({html_len})",
+        V::MIN_LENGTH
+    );
+    assert!(
+        html_len <= rendered_len,
+        "html_len ({html_len}) is more than the length of the rendered HTML \
+         ({rendered_len})"
+    );
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::assert_min_length_valid;
+    use crate::{
+        html::{attribute::global::GlobalAttributes, element::*},
+        mathml, svg,
+    };
+
+    macro_rules! check_void_elements {
+        ($($tag:ident),* $(,)?) => {
+            $(
+                assert_min_length_valid($tag());
+                assert_min_length_valid($tag().id("a"));
+            )*
+        };
+    }
+
+    macro_rules! check_elements {
+        ($($tag:ident),* $(,)?) => {
+            $(
+                assert_min_length_valid($tag());
+                assert_min_length_valid($tag().id("a").child("text"));
+            )*
+        };
+    }
+
+    #[test]
+    fn void_elements_have_valid_min_length() {
+        check_void_elements!(
+            area, base, br, col, embed, hr, img, input, link, meta, source,
+            track, wbr,
+        );
+    }
+
+    #[test]
+    fn elements_have_valid_min_length() {
+        check_elements!(
+            a, abbr, address, article, aside, audio, b, bdi, bdo, blockquote,
+            body, button, canvas, caption, cite, code, colgroup, data,
+            datalist, dd, del, details, dfn, dialog, div, dl, dt, em, fieldset,
+            figcaption, figure, footer, form, head, header, hgroup, html, i,
+            iframe, ins, kbd, label, legend, li, main, map, mark, menu, meter,
+            nav, noscript, object, ol, optgroup, output, p, picture, portal,
+            pre, progress, q, rp, rt, ruby, s, samp, script, search, section,
+            select, slot, small, span, strong, style, sub, summary, sup, table,
+            tbody, td, template, textarea, tfoot, th, thead, time, title, tr,
+            u, ul, var, video,
+        );
+    }
+
+    #[test]
+    fn nested_elements_have_valid_min_length() {
+        assert_min_length_valid(
+            ul().child((li().child("a"), li().child(img().id("b")))),
+        );
+        assert_min_length_valid(mathml::math().child(mathml::mn().child("1")));
+        assert_min_length_valid(svg::svg().child(svg::circle()));
+    }
+}
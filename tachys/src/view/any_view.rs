@@ -126,6 +126,25 @@ pub trait IntoAny {
     fn into_any(self) -> AnyView;
 }
 
+/// Creates a `Vec` of [`AnyView`] from views of different types, converting each one with
+/// [`IntoAny::into_any`].
+///
+/// This makes it easy to build a dynamic list of different elements, which can then be rendered
+/// like any other `Vec` of views.
+///
+/// ```
+/// # use tachys::prelude::*;
+/// # use tachys::{html::element::{div, p}, view::any_view::AnyView, views};
+/// let list: Vec<AnyView> = views![p().child("text"), div().child("box")];
+/// assert_eq!(list.len(), 2);
+/// ```
+#[macro_export]
+macro_rules! views {
+    ($($view:expr),* $(,)?) => {
+        ::std::vec![$($crate::view::any_view::IntoAny::into_any($view)),*]
+    };
+}
+
 /// A more general version of [`IntoAny`] that allows into [`AnyView`],
 /// but also erasing other types that don't implement [`RenderHtml`] like routing.
 pub trait IntoMaybeErased {
@@ -879,3 +898,32 @@ mod tests {
     }
 }
  */
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::IntoAny;
+    use crate::{
+        html::element::{div, em, li, p, ul, ElementChild},
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn views_renders_mixed_elements_in_order() {
+        let list = views![p().child("text"), div().child("box")];
+        assert_eq!(list.to_html(), "<p>text</p><div>box</div><!>");
+    }
+
+    #[test]
+    fn branch_markers_only_wrap_dynamic_views() {
+        let markers = |html: String| html.matches("<!--b").count();
+
+        let static_tree =
+            ul().child((li().child("one"), li().child(("two", em().child(2)))));
+        const { assert!(!<(&str,) as RenderHtml>::DYNAMIC) };
+        assert_eq!(markers(static_tree.to_html_branching()), 0);
+
+        let dynamic_tree =
+            ul().child((li().child("one"), li().child("two".into_any())));
+        assert_eq!(markers(dynamic_tree.to_html_branching()), 2);
+    }
+}
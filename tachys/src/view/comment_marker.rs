@@ -0,0 +1,136 @@
+use super::{
+    add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml,
+    ToTemplate,
+};
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::Cursor,
+    renderer::{types::Placeholder, Rndr},
+};
+
+/// The text of the comment rendered by [`CommentMarker`].
+const HOLE_MARKER: &str = "hk";
+
+/// A `<!--hk-->` comment that marks a hole in a view, where dynamic content can be inserted.
+///
+/// Unlike text or elements, it also appears in [`ToTemplate::TEMPLATE`], so views that are
+/// cloned from a `<template>` keep a stable node to hydrate and to insert content before. When
+/// hydrating, the cursor moves onto the comment, and the comment is returned as the view's
+/// state.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CommentMarker;
+
+impl Render for CommentMarker {
+    type State = Placeholder;
+
+    fn build(self) -> Self::State {
+        let marker = Rndr::create_placeholder();
+        marker.set_text_content(Some(HOLE_MARKER));
+        marker
+    }
+
+    fn rebuild(self, _state: &mut Self::State) {}
+}
+
+impl AddAnyAttr for CommentMarker {
+    type Output<SomeNewAttr: Attribute> = CommentMarker;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        _attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        self
+    }
+}
+
+impl RenderHtml for CommentMarker {
+    type AsyncOutput = Self;
+    type Owned = Self;
+
+    const MIN_LENGTH: usize = "<!--hk-->".len();
+    const DYNAMIC: bool = false;
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        _escape: bool,
+        _mark_branches: bool,
+        _extra_attrs: Vec<AnyAttribute>,
+    ) {
+        buf.push_str(Self::TEMPLATE);
+        *position = Position::NextChild;
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        let marker = cursor.next_placeholder(position);
+        debug_assert_eq!(
+            marker.text_content().as_deref(),
+            Some(HOLE_MARKER),
+            "expected a <!--{HOLE_MARKER}--> comment while hydrating a \
+             CommentMarker"
+        );
+        marker
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
+
+impl ToTemplate for CommentMarker {
+    const TEMPLATE: &'static str = "<!--hk-->";
+
+    fn to_template(
+        buf: &mut String,
+        _class: &mut String,
+        _style: &mut String,
+        _inner_html: &mut String,
+        position: &mut Position,
+    ) {
+        buf.push_str(Self::TEMPLATE);
+        *position = Position::NextChild;
+    }
+
+    fn to_template_attribute(
+        _buf: &mut String,
+        _class: &mut String,
+        _style: &mut String,
+        _inner_html: &mut String,
+        _position: &mut Position,
+    ) {
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::CommentMarker;
+    use crate::{
+        html::element::{li, ul, ElementChild},
+        view::{RenderHtml, ToTemplate},
+    };
+
+    fn template_of<V: ToTemplate>(_view: &V) -> &'static str {
+        V::TEMPLATE
+    }
+
+    #[test]
+    fn marker_is_part_of_the_template() {
+        let view = ul().child((li().child("a"), CommentMarker));
+        assert!(template_of(&view).ends_with("<!--hk--></ul>"));
+        assert_eq!(view.to_html(), "<ul><li>a</li><!--hk--></ul>");
+    }
+}
@@ -0,0 +1,441 @@
+use super::{
+    add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+    RenderHtml,
+};
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::{failed_to_cast_marker_node, Cursor},
+    renderer::{
+        dom::queue_microtask,
+        types::{Element, Node, Placeholder},
+        CastFrom, Rndr,
+    },
+    ssr::StreamBuilder,
+};
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+use wasm_bindgen::{prelude::Closure, JsCast};
+use web_sys::{IntersectionObserver, IntersectionObserverEntry};
+
+/// The comment that opens a deferred section of server-rendered HTML.
+pub const DEFER_OPEN: &str = "<!--defer-o-->";
+/// The comment that closes a deferred section of server-rendered HTML.
+pub const DEFER_CLOSE: &str = "<!--defer-c-->";
+
+/// A wrapper that renders its view to HTML as usual, but only hydrates it once it is scrolled
+/// into view.
+///
+/// This is meant for content below the fold: it is still part of the server-rendered page, so it
+/// is visible and indexable right away, but the work of hydrating it is put off until it is
+/// needed.
+///
+/// # The marker
+/// On the server, the view's HTML is wrapped in a pair of comments, [`DEFER_OPEN`]
+/// (`<!--defer-o-->`) and [`DEFER_CLOSE`] (`<!--defer-c-->`). Nothing else is added, so the HTML
+/// between them is exactly what the view renders to. Deferred sections can be nested; each
+/// opening marker is matched by the next closing marker at the same depth.
+///
+/// # The client contract
+/// When the page is hydrated, the nodes between the markers are left as they are, and hydration
+/// carries on after the closing marker. An [`IntersectionObserver`] watches every element
+/// between the markers, and as soon as one of them intersects the viewport, the view is
+/// hydrated against those nodes, inside the reactive owner that was current when hydration
+/// first reached it. Until then, the server-rendered HTML is inert: it has no event listeners and
+/// does not update. If there are no elements to observe, the view is hydrated immediately.
+///
+/// Updates to the view that arrive before it has been hydrated replace the view that will be
+/// hydrated, so it is hydrated in its latest state. When the view is created on the client
+/// rather than hydrated, it is built right away.
+#[derive(Debug, Clone)]
+pub struct DeferHydration<V> {
+    view: V,
+}
+
+impl<V> DeferHydration<V> {
+    /// Wraps the view, so that its hydration is deferred until it is visible.
+    pub fn new(view: V) -> Self {
+        Self { view }
+    }
+}
+
+/// Retained view state for [`DeferHydration`].
+pub struct DeferHydrationState<V>
+where
+    V: Render,
+{
+    start: Placeholder,
+    end: Placeholder,
+    inner: Rc<RefCell<Deferred<V>>>,
+}
+
+struct Deferred<V>
+where
+    V: Render,
+{
+    pending: Option<Pending<V>>,
+    state: Option<V::State>,
+}
+
+/// A view that has not been hydrated yet, along with the server-rendered nodes it will be
+/// hydrated against.
+struct Pending<V> {
+    view: V,
+    nodes: Vec<Node>,
+    #[cfg(feature = "reactive_graph")]
+    owner: reactive_graph::owner::Owner,
+    observer: Option<Observer>,
+}
+
+struct Observer {
+    observer: IntersectionObserver,
+    _callback: Closure<dyn FnMut(js_sys::Array)>,
+}
+
+impl Drop for Observer {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl<V> DeferHydrationState<V>
+where
+    V: RenderHtml + 'static,
+{
+    fn observe(&self, elements: &[Element]) -> Option<Observer> {
+        let start = self.start.clone();
+        let inner = Rc::downgrade(&self.inner);
+        let callback = Closure::<dyn FnMut(js_sys::Array)>::new(
+            move |entries: js_sys::Array| {
+                let visible = entries.iter().any(|entry| {
+                    entry
+                        .unchecked_into::<IntersectionObserverEntry>()
+                        .is_intersecting()
+                });
+                if visible {
+                    // hydrating drops this closure, so it can't happen while it is running
+                    let start = start.clone();
+                    let inner = Weak::clone(&inner);
+                    queue_microtask(move || {
+                        if let Some(inner) = inner.upgrade() {
+                            hydrate_deferred(&start, &inner);
+                        }
+                    });
+                }
+            },
+        );
+        let observer =
+            IntersectionObserver::new(callback.as_ref().unchecked_ref())
+                .ok()?;
+        for el in elements {
+            observer.observe(el);
+        }
+        Some(Observer {
+            observer,
+            _callback: callback,
+        })
+    }
+}
+
+/// Hydrates the pending view against the nodes that follow the `start` marker.
+fn hydrate_deferred<V>(start: &Placeholder, inner: &RefCell<Deferred<V>>)
+where
+    V: RenderHtml,
+{
+    let Some(parent) =
+        Rndr::get_parent(start.as_ref()).and_then(Element::cast_from)
+    else {
+        // not mounted, so it will be hydrated when it is next visible
+        return;
+    };
+    let Some(pending) = inner.borrow_mut().pending.take() else {
+        return;
+    };
+    drop(pending.observer);
+    let cursor = Cursor::new(parent);
+    cursor.set(start.clone().into());
+    let position = PositionState::new(Position::NextChild);
+    let hydrate = || pending.view.hydrate::<true>(&cursor, &position);
+    #[cfg(feature = "reactive_graph")]
+    let state = pending.owner.with(hydrate);
+    #[cfg(not(feature = "reactive_graph"))]
+    let state = hydrate();
+    inner.borrow_mut().state = Some(state);
+}
+
+impl<V> Mountable for DeferHydrationState<V>
+where
+    V: Render,
+{
+    fn unmount(&mut self) {
+        self.start.unmount();
+        let mut inner = self.inner.borrow_mut();
+        if let Some(pending) = &inner.pending {
+            for node in &pending.nodes {
+                Rndr::remove(node);
+            }
+        }
+        if let Some(state) = &mut inner.state {
+            state.unmount();
+        }
+        self.end.unmount();
+    }
+
+    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+        self.start.mount(parent, marker);
+        let mut inner = self.inner.borrow_mut();
+        if let Some(pending) = &inner.pending {
+            for node in &pending.nodes {
+                Rndr::insert_node(parent, node, marker);
+            }
+        }
+        if let Some(state) = &mut inner.state {
+            state.mount(parent, marker);
+        }
+        self.end.mount(parent, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        self.start.insert_before_this(child)
+    }
+
+    fn elements(&self) -> Vec<Element> {
+        let inner = self.inner.borrow();
+        match (&inner.pending, &inner.state) {
+            (Some(pending), _) => pending
+                .nodes
+                .iter()
+                .filter_map(|node| Element::cast_from(node.clone()))
+                .collect(),
+            (None, Some(state)) => state.elements(),
+            (None, None) => vec![],
+        }
+    }
+}
+
+impl<V> Render for DeferHydration<V>
+where
+    V: RenderHtml,
+{
+    type State = DeferHydrationState<V::Owned>;
+
+    fn build(self) -> Self::State {
+        DeferHydrationState {
+            start: Rndr::create_placeholder(),
+            end: Rndr::create_placeholder(),
+            inner: Rc::new(RefCell::new(Deferred {
+                pending: None,
+                state: Some(self.view.into_owned().build()),
+            })),
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let view = self.view.into_owned();
+        let mut inner = state.inner.borrow_mut();
+        if let Some(pending) = &mut inner.pending {
+            pending.view = view;
+        } else if let Some(state) = &mut inner.state {
+            view.rebuild(state);
+        }
+    }
+}
+
+impl<V> AddAnyAttr for DeferHydration<V>
+where
+    V: AddAnyAttr,
+{
+    type Output<SomeNewAttr: Attribute> =
+        DeferHydration<V::Output<SomeNewAttr>>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        DeferHydration {
+            view: self.view.add_any_attr(attr),
+        }
+    }
+}
+
+impl<V> RenderHtml for DeferHydration<V>
+where
+    V: RenderHtml,
+{
+    type AsyncOutput = DeferHydration<V::AsyncOutput>;
+    type Owned = DeferHydration<V::Owned>;
+
+    const MIN_LENGTH: usize =
+        DEFER_OPEN.len() + V::MIN_LENGTH + DEFER_CLOSE.len();
+
+    fn html_len(&self) -> usize {
+        DEFER_OPEN.len() + self.view.html_len() + DEFER_CLOSE.len()
+    }
+
+    fn dry_resolve(&mut self) {
+        self.view.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        DeferHydration {
+            view: self.view.resolve().await,
+        }
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) {
+        buf.push_str(DEFER_OPEN);
+        *position = Position::NextChild;
+        self.view.to_html_with_buf(
+            buf,
+            position,
+            escape,
+            mark_branches,
+            extra_attrs,
+        );
+        buf.push_str(DEFER_CLOSE);
+        *position = Position::NextChild;
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) where
+        Self: Sized,
+    {
+        buf.push_sync(DEFER_OPEN);
+        *position = Position::NextChild;
+        self.view.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+            extra_attrs,
+        );
+        buf.push_sync(DEFER_CLOSE);
+        *position = Position::NextChild;
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        let start = cursor.next_placeholder(position);
+
+        // skip over the server-rendered nodes, up to the matching closing marker
+        let mut nodes = Vec::new();
+        let mut depth = 0;
+        let mut next = Rndr::next_sibling(start.as_ref());
+        let end = loop {
+            let Some(node) = next else {
+                // the parent ended without the closing marker
+                let last: &Node = nodes.last().unwrap_or(start.as_ref());
+                break failed_to_cast_marker_node(last.clone());
+            };
+            if node.node_type() == Node::COMMENT_NODE {
+                match node.text_content().as_deref() {
+                    Some("defer-o") => depth += 1,
+                    Some("defer-c") if depth == 0 => {
+                        break node.unchecked_into::<Placeholder>()
+                    }
+                    Some("defer-c") => depth -= 1,
+                    _ => {}
+                }
+            }
+            next = Rndr::next_sibling(&node);
+            nodes.push(node);
+        };
+        cursor.set(end.clone().into());
+        position.set(Position::NextChild);
+
+        let elements = nodes
+            .iter()
+            .filter_map(|node| Element::cast_from(node.clone()))
+            .collect::<Vec<_>>();
+        let state = DeferHydrationState {
+            start,
+            end,
+            inner: Rc::new(RefCell::new(Deferred {
+                pending: Some(Pending {
+                    view: self.view.into_owned(),
+                    nodes,
+                    #[cfg(feature = "reactive_graph")]
+                    owner: reactive_graph::owner::Owner::current()
+                        .unwrap_or_default(),
+                    observer: None,
+                }),
+                state: None,
+            })),
+        };
+        let observer = if elements.is_empty() {
+            None
+        } else {
+            state.observe(&elements)
+        };
+        match observer {
+            Some(observer) => {
+                if let Some(pending) = &mut state.inner.borrow_mut().pending {
+                    pending.observer = Some(observer);
+                }
+            }
+            None => hydrate_deferred(&state.start, &state.inner),
+        }
+        state
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        DeferHydration {
+            view: self.view.into_owned(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::{DeferHydration, DEFER_CLOSE, DEFER_OPEN};
+    use crate::{
+        html::element::{footer, p, section, ElementChild},
+        view::RenderHtml,
+    };
+    use futures::{executor::block_on, StreamExt};
+
+    #[test]
+    fn renders_complete_html_between_markers() {
+        let view = || {
+            (
+                p().child("above the fold"),
+                DeferHydration::new(section().child(p().child("below"))),
+                footer(),
+            )
+        };
+        let html = view().to_html();
+        let deferred = "<section><p>below</p></section>";
+        assert_eq!(
+            html,
+            format!(
+                "<p>above the fold</p>{DEFER_OPEN}{deferred}{DEFER_CLOSE}\
+                 <footer></footer>"
+            )
+        );
+        assert_eq!(view().html_len(), html.len());
+        assert_eq!(
+            block_on(view().to_html_stream_in_order().collect::<String>()),
+            html
+        );
+    }
+}
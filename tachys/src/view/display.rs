@@ -0,0 +1,156 @@
+use super::{
+    add_attr::AddAnyAttr, strings::escape_text, Position, PositionState,
+    Render, RenderHtml, ToTemplate,
+};
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::Cursor,
+};
+use std::fmt::{self, Display, Write};
+
+/// Renders any [`Display`] type as a text node, without converting it to a `String` first.
+///
+/// When rendering to HTML, the formatted output is escaped as it is written into the buffer.
+/// In the browser, it is formatted into a `String` to create or update the text node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DisplayView<T>(pub T);
+
+/// Escapes everything written to it into the buffer.
+struct EscapingWriter<'a>(&'a mut String);
+
+impl Write for EscapingWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        escape_text(s, self.0);
+        Ok(())
+    }
+}
+
+impl<T> Render for DisplayView<T>
+where
+    T: Display,
+{
+    type State = <String as Render>::State;
+
+    fn build(self) -> Self::State {
+        self.0.to_string().build()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.0.to_string().rebuild(state);
+    }
+}
+
+impl<T> AddAnyAttr for DisplayView<T>
+where
+    T: Display + Send,
+{
+    type Output<SomeNewAttr: Attribute> = Self;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        _attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        self
+    }
+}
+
+impl<T> RenderHtml for DisplayView<T>
+where
+    T: Display + Send,
+{
+    type AsyncOutput = Self;
+    type Owned = String;
+
+    const MIN_LENGTH: usize = 0;
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        _mark_branches: bool,
+        _extra_attrs: Vec<AnyAttribute>,
+    ) {
+        // add a comment node to separate from previous sibling, if any
+        if matches!(position, Position::NextChildAfterText) {
+            buf.push_str("<!>")
+        }
+        let start = buf.len();
+        // writing into a `String` can't fail, so an error can only come from a broken `Display`
+        // implementation, whose partial output is kept
+        _ = if escape {
+            write!(EscapingWriter(&mut *buf), "{}", self.0)
+        } else {
+            write!(buf, "{}", self.0)
+        };
+        // like an empty string, render a space so there is a text node to hydrate
+        if buf.len() == start && escape {
+            buf.push(' ');
+        }
+        *position = Position::NextChildAfterText;
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.0.to_string().hydrate::<FROM_SERVER>(cursor, position)
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self.0.to_string()
+    }
+}
+
+impl<T> ToTemplate for DisplayView<T> {
+    const TEMPLATE: &'static str = <String as ToTemplate>::TEMPLATE;
+
+    fn to_template(
+        buf: &mut String,
+        class: &mut String,
+        style: &mut String,
+        inner_html: &mut String,
+        position: &mut Position,
+    ) {
+        <String as ToTemplate>::to_template(
+            buf, class, style, inner_html, position,
+        )
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::DisplayView;
+    use crate::{
+        html::element::{p, ElementChild},
+        view::RenderHtml,
+    };
+    use std::fmt;
+
+    struct Comparison(u32, u32);
+
+    impl fmt::Display for Comparison {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "{} < {} && {}", self.0, self.1, self.1 > self.0)
+        }
+    }
+
+    #[test]
+    fn display_output_is_escaped() {
+        let html = p().child(DisplayView(Comparison(1, 2))).to_html();
+        assert_eq!(html, "<p>1 &lt; 2 &amp;&amp; true</p>");
+    }
+
+    #[test]
+    fn empty_display_output_renders_a_text_node() {
+        let html = p().child((DisplayView(""), DisplayView(3))).to_html();
+        assert_eq!(html, "<p> <!>3</p>");
+    }
+}
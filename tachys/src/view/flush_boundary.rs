@@ -0,0 +1,178 @@
+use super::{
+    add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml,
+};
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::Cursor,
+    ssr::StreamBuilder,
+};
+
+/// A transparent wrapper that ends the current chunk of an HTML stream after its view.
+///
+/// When streaming, synchronous HTML is normally combined into as few chunks as possible. Wrapping
+/// above-the-fold content in a `FlushBoundary` sends it to the browser as soon as it has been
+/// rendered, rather than together with whatever follows it.
+///
+/// When rendering to a string or hydrating, it has no effect.
+#[derive(Debug, Clone)]
+pub struct FlushBoundary<V> {
+    view: V,
+}
+
+impl<V> FlushBoundary<V> {
+    /// Wraps the view, so that the stream is flushed after it.
+    pub fn new(view: V) -> Self {
+        Self { view }
+    }
+}
+
+impl<V> Render for FlushBoundary<V>
+where
+    V: Render,
+{
+    type State = V::State;
+
+    fn build(self) -> Self::State {
+        self.view.build()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.view.rebuild(state);
+    }
+}
+
+impl<V> AddAnyAttr for FlushBoundary<V>
+where
+    V: AddAnyAttr,
+{
+    type Output<SomeNewAttr: Attribute> = FlushBoundary<V::Output<SomeNewAttr>>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        FlushBoundary {
+            view: self.view.add_any_attr(attr),
+        }
+    }
+}
+
+impl<V> RenderHtml for FlushBoundary<V>
+where
+    V: RenderHtml,
+{
+    type AsyncOutput = FlushBoundary<V::AsyncOutput>;
+    type Owned = FlushBoundary<V::Owned>;
+
+    const MIN_LENGTH: usize = V::MIN_LENGTH;
+    const EXISTS: bool = V::EXISTS;
+    const DYNAMIC: bool = V::DYNAMIC;
+
+    fn html_len(&self) -> usize {
+        self.view.html_len()
+    }
+
+    fn dry_resolve(&mut self) {
+        self.view.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        FlushBoundary {
+            view: self.view.resolve().await,
+        }
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) {
+        self.view.to_html_with_buf(
+            buf,
+            position,
+            escape,
+            mark_branches,
+            extra_attrs,
+        );
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) where
+        Self: Sized,
+    {
+        self.view.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+            extra_attrs,
+        );
+        buf.flush_chunk();
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.view.hydrate::<FROM_SERVER>(cursor, position)
+    }
+
+    async fn hydrate_async(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.view.hydrate_async(cursor, position).await
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        FlushBoundary {
+            view: self.view.into_owned(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::FlushBoundary;
+    use crate::{
+        html::element::{footer, header, main, ElementChild},
+        view::RenderHtml,
+    };
+    use futures::{executor::block_on, StreamExt};
+
+    #[test]
+    fn each_boundary_ends_a_chunk() {
+        let view = || {
+            (
+                FlushBoundary::new(header().child("header")),
+                FlushBoundary::new(main().child("main")),
+                footer(),
+            )
+        };
+        let chunks =
+            block_on(view().to_html_stream_in_order().collect::<Vec<_>>());
+        assert_eq!(
+            chunks,
+            [
+                "<header>header</header>",
+                "<main>main</main>",
+                "<footer></footer>"
+            ]
+        );
+        assert_eq!(chunks.concat(), view().to_html());
+    }
+}
@@ -0,0 +1,140 @@
+use super::{
+    add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+    RenderHtml,
+};
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::Cursor,
+    renderer::{
+        types::{Element, Node},
+        CastFrom, Rndr,
+    },
+};
+use send_wrapper::SendWrapper;
+
+/// A view that mounts an existing DOM node, which was created outside of this library.
+///
+/// This can be used to embed DOM that is managed imperatively, like a chart created by a
+/// JavaScript library, into the view tree. The node is inserted as-is, rather than being
+/// re-created. Unmounting the view detaches the node from its parent without destroying it, so
+/// it can be mounted again later.
+///
+/// The node only exists on the client: when rendered to HTML, this renders a placeholder
+/// comment, which is replaced by the node during hydration.
+pub struct ForeignNode {
+    node: SendWrapper<Node>,
+}
+
+impl ForeignNode {
+    /// Wraps an existing DOM node.
+    pub fn new(node: impl Into<Node>) -> Self {
+        Self {
+            node: SendWrapper::new(node.into()),
+        }
+    }
+}
+
+/// Retained view state for [`ForeignNode`].
+pub struct ForeignNodeState {
+    node: Node,
+}
+
+impl Mountable for ForeignNodeState {
+    fn unmount(&mut self) {
+        Rndr::remove(&self.node);
+    }
+
+    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+        Rndr::insert_node(parent, &self.node, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        self.node.insert_before_this(child)
+    }
+
+    fn elements(&self) -> Vec<Element> {
+        Element::cast_from(self.node.clone()).into_iter().collect()
+    }
+}
+
+impl Render for ForeignNode {
+    type State = ForeignNodeState;
+
+    fn build(self) -> Self::State {
+        ForeignNodeState {
+            node: self.node.take(),
+        }
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        let node = self.node.take();
+        if node != state.node {
+            let mut new_state = ForeignNodeState { node };
+            state.insert_before_this(&mut new_state);
+            state.unmount();
+            *state = new_state;
+        }
+    }
+}
+
+impl AddAnyAttr for ForeignNode {
+    type Output<SomeNewAttr: Attribute> = Self;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        _attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        self
+    }
+}
+
+impl RenderHtml for ForeignNode {
+    type AsyncOutput = Self;
+    type Owned = Self;
+
+    const MIN_LENGTH: usize = "<!>".len();
+
+    fn html_len(&self) -> usize {
+        Self::MIN_LENGTH
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        _escape: bool,
+        _mark_branches: bool,
+        _extra_attrs: Vec<AnyAttribute>,
+    ) {
+        buf.push_str("<!>");
+        *position = Position::NextChild;
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        // swap the placeholder rendered on the server for the node
+        let mut marker = cursor.next_placeholder(position);
+        let node = self.node.take();
+        let mut state = ForeignNodeState { node: node.clone() };
+        marker.insert_before_this(&mut state);
+        marker.unmount();
+        cursor.set(node);
+        state
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
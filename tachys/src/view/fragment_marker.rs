@@ -0,0 +1,230 @@
+use super::{
+    add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml,
+};
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::Cursor,
+    ssr::{next_fragment_id, RenderContext, StreamBuilder},
+};
+use std::borrow::Cow;
+
+/// A transparent wrapper that marks where its view starts and ends in the rendered HTML, when
+/// [`fragment_markers`](RenderContext::fragment_markers) is turned on.
+///
+/// The view is wrapped in `<!--$id-->` and `<!--/$id-->`, so that a server doing partial page
+/// updates can render just this fragment again, and the client can replace everything between
+/// the two comments with the same `id`. The id is either given with
+/// [`with_id`](FragmentMarker::with_id), or generated as `f` followed by the fragment's index
+/// among the generated ids inside [`run`](RenderContext::run), in document order (`f0`, `f1`,
+/// and so on). Generated ids only depend on the structure of the view, so rendering the same
+/// view again gives the same ids.
+#[derive(Debug, Clone)]
+pub struct FragmentMarker<V> {
+    id: Option<Cow<'static, str>>,
+    view: V,
+}
+
+impl<V> FragmentMarker<V> {
+    /// Wraps the view in a fragment with a generated id.
+    pub fn new(view: V) -> Self {
+        Self { id: None, view }
+    }
+
+    /// Wraps the view in a fragment with the given id.
+    ///
+    /// The id ends up inside an HTML comment, so it should not contain `--` or `>`.
+    pub fn with_id(id: impl Into<Cow<'static, str>>, view: V) -> Self {
+        let id = id.into();
+        debug_assert!(
+            !id.contains("--") && !id.contains('>'),
+            "fragment id {id:?} cannot be used inside an HTML comment"
+        );
+        Self { id: Some(id), view }
+    }
+
+    /// The id of the fragment, or `None` if the markers are turned off.
+    fn marker_id(&self) -> Option<Cow<'static, str>> {
+        if !RenderContext::with_current(|cx| cx.fragment_markers) {
+            return None;
+        }
+        Some(match &self.id {
+            Some(id) => id.clone(),
+            None => format!("f{}", next_fragment_id()).into(),
+        })
+    }
+}
+
+impl<V> Render for FragmentMarker<V>
+where
+    V: Render,
+{
+    type State = V::State;
+
+    fn build(self) -> Self::State {
+        self.view.build()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.view.rebuild(state);
+    }
+}
+
+impl<V> AddAnyAttr for FragmentMarker<V>
+where
+    V: AddAnyAttr,
+{
+    type Output<SomeNewAttr: Attribute> =
+        FragmentMarker<V::Output<SomeNewAttr>>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        FragmentMarker {
+            id: self.id,
+            view: self.view.add_any_attr(attr),
+        }
+    }
+}
+
+impl<V> RenderHtml for FragmentMarker<V>
+where
+    V: RenderHtml,
+{
+    type AsyncOutput = FragmentMarker<V::AsyncOutput>;
+    type Owned = FragmentMarker<V::Owned>;
+
+    const MIN_LENGTH: usize = V::MIN_LENGTH;
+    const EXISTS: bool = V::EXISTS;
+    const DYNAMIC: bool = V::DYNAMIC;
+
+    fn html_len(&self) -> usize {
+        self.view.html_len()
+    }
+
+    fn dry_resolve(&mut self) {
+        self.view.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        FragmentMarker {
+            id: self.id,
+            view: self.view.resolve().await,
+        }
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) {
+        let id = self.marker_id();
+        if let Some(id) = &id {
+            buf.push_str("<!--$");
+            buf.push_str(id);
+            buf.push_str("-->");
+        }
+        self.view.to_html_with_buf(
+            buf,
+            position,
+            escape,
+            mark_branches,
+            extra_attrs,
+        );
+        if let Some(id) = &id {
+            buf.push_str("<!--/$");
+            buf.push_str(id);
+            buf.push_str("-->");
+        }
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) where
+        Self: Sized,
+    {
+        let id = self.marker_id();
+        if let Some(id) = &id {
+            buf.push_sync(&format!("<!--${id}-->"));
+        }
+        self.view.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+            extra_attrs,
+        );
+        if let Some(id) = &id {
+            buf.push_sync(&format!("<!--/${id}-->"));
+        }
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.view.hydrate::<FROM_SERVER>(cursor, position)
+    }
+
+    async fn hydrate_async(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.view.hydrate_async(cursor, position).await
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        FragmentMarker {
+            id: self.id,
+            view: self.view.into_owned(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::FragmentMarker;
+    use crate::{
+        html::element::{footer, header, main, ElementChild},
+        ssr::RenderContext,
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn top_level_views_are_wrapped_in_matching_markers() {
+        let page = || {
+            (
+                FragmentMarker::new(header().child("a")),
+                FragmentMarker::with_id("main", main().child("b")),
+                FragmentMarker::new(footer().child("c")),
+            )
+        };
+        let html = RenderContext::new()
+            .fragment_markers(true)
+            .run(|| page().to_html());
+        assert_eq!(
+            html,
+            "<!--$f0--><header>a</header><!--/$f0-->\
+             <!--$main--><main>b</main><!--/$main-->\
+             <!--$f1--><footer>c</footer><!--/$f1-->"
+        );
+
+        assert_eq!(
+            page().to_html(),
+            "<header>a</header><main>b</main><footer>c</footer>"
+        );
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     html::attribute::{any_attribute::AnyAttribute, Attribute},
     hydration::Cursor,
     renderer::Rndr,
-    ssr::StreamBuilder,
+    ssr::{html_limit_reached, StreamBuilder},
 };
 use either_of::Either;
 use itertools::Itertools;
@@ -332,6 +332,9 @@ where
             );
         }
         for child in children {
+            if html_limit_reached(buf) {
+                break;
+            }
             child.to_html_with_buf(
                 buf,
                 position,
@@ -358,16 +361,7 @@ where
         Self: Sized,
     {
         let mut children = self.into_iter();
-        if let Some(first) = children.next() {
-            first.to_html_async_with_buf::<OUT_OF_ORDER>(
-                buf,
-                position,
-                escape,
-                mark_branches,
-                extra_attrs.clone(),
-            );
-        }
-        for child in children {
+        while let Some(child) = children.next() {
             child.to_html_async_with_buf::<OUT_OF_ORDER>(
                 buf,
                 position,
@@ -375,6 +369,20 @@ where
                 mark_branches,
                 extra_attrs.clone(),
             );
+
+            // the rest of a long list is rendered once the stream has caught up with this part
+            if buf.over_chunk_budget() && children.len() > 0 {
+                let rest = children.map(T::into_owned).collect::<Vec<_>>();
+                buf.push_deferred::<OUT_OF_ORDER, _>(
+                    rest,
+                    position,
+                    escape,
+                    mark_branches,
+                    extra_attrs,
+                );
+                *position = Position::NextChild;
+                return;
+            }
         }
         if escape {
             buf.push_sync("<!>");
@@ -621,6 +629,9 @@ where
         extra_attrs: Vec<AnyAttribute>,
     ) {
         for child in self.0.into_iter() {
+            if html_limit_reached(buf) {
+                break;
+            }
             child.to_html_with_buf(
                 buf,
                 position,
@@ -822,6 +833,9 @@ where
         extra_attrs: Vec<AnyAttribute>,
     ) {
         for child in self.into_iter() {
+            if html_limit_reached(buf) {
+                break;
+            }
             child.to_html_with_buf(
                 buf,
                 position,
@@ -302,6 +302,9 @@ where
 
         #[cfg(feature = "ssr")]
         for item in self.ssr_items {
+            if crate::ssr::html_limit_reached(buf) {
+                break;
+            }
             if mark_branches && escape {
                 buf.open_branch("item");
             }
@@ -1,38 +1,70 @@
 use self::add_attr::AddAnyAttr;
 use crate::{
-    html::attribute::any_attribute::AnyAttribute, hydration::Cursor,
-    ssr::StreamBuilder,
+    html::attribute::any_attribute::AnyAttribute,
+    hydration::Cursor,
+    ssr::{
+        check_html_limit, collect_assets, strip_if_enabled, AssetRef,
+        RenderContext, StreamBuilder,
+    },
 };
+use futures::Stream;
 use or_poisoned::OrPoisoned;
 use std::{
     cell::RefCell,
+    error::Error,
+    fmt,
     future::Future,
     rc::Rc,
+    str::FromStr,
     sync::{Arc, RwLock},
 };
 
+/// The chunk size used by [`RenderHtml::to_html_stream`] when the render context doesn't set
+/// one.
+const DEFAULT_HTML_CHUNK_BUDGET: usize = 16 * 1024;
+
 /// Add attributes to typed views.
 pub mod add_attr;
 /// A typed-erased view type.
 pub mod any_view;
+/// A comment that marks a hole in a view, for hydration.
+pub mod comment_marker;
+/// A wrapper that defers hydrating a view until it is scrolled into view.
+pub mod defer_hydration;
+/// A view that renders any `Display` type as text.
+pub mod display;
 /// Allows choosing between one of several views.
 pub mod either;
 /// View rendering for `Result<_, _>` types.
 pub mod error_boundary;
+/// A wrapper that flushes the HTML stream after a view.
+pub mod flush_boundary;
+/// A view that mounts an existing DOM node.
+pub mod foreign_node;
 /// A type-erased view collection.
 pub mod fragment;
+/// A wrapper that marks where a view starts and ends, for partial page updates.
+pub mod fragment_marker;
 /// View implementations for several iterable types.
 pub mod iterators;
 /// Keyed list iteration.
 pub mod keyed;
 mod primitives;
+/// A wrapper that reports the size of the HTML a view renders to.
+pub mod size_probe;
 /// Optimized types for static strings known at compile time.
 #[cfg(all(feature = "nightly", rustc_nightly))]
 pub mod static_types;
+/// A view that streams trusted HTML from an async reader.
+pub mod stream_from;
 /// View implementation for string types.
 pub mod strings;
 /// Optimizations for creating views via HTML `<template>` nodes.
 pub mod template;
+/// Renders durations, and `chrono` dates and times, as `<time>` elements with an ISO 8601
+/// `datetime` attribute and human-readable text.
+#[cfg(feature = "time")]
+pub mod time;
 /// View implementations for tuples.
 pub mod tuples;
 
@@ -114,6 +146,14 @@ where
     /// Whether this should actually exist in the DOM, if it is the child of an element.
     const EXISTS: bool = true;
 
+    /// Whether the structure of this view can differ between renders, like a branch that
+    /// renders one of several types or a list whose length can change.
+    ///
+    /// Branch markers are only written around views that are dynamic, when
+    /// [rendering with them](RenderHtml::to_html_branching). Text, elements whose children are
+    /// all static, and other views that always render the same structure set this to `false`.
+    const DYNAMIC: bool = true;
+
     /// “Runs” the view without other side effects. For primitive types, this is a no-op. For
     /// reactive types, this can be used to gather data about reactivity or about asynchronous data
     /// that needs to be loaded.
@@ -144,7 +184,32 @@ where
             false,
             vec![],
         );
-        buf
+        check_html_limit(strip_if_enabled(buf))
+    }
+
+    /// Renders a view to an indented HTML string, with nested elements on their own lines.
+    ///
+    /// This renders with the current [`RenderContext`](crate::ssr::RenderContext), with
+    /// [`pretty`](crate::ssr::RenderContext::pretty) turned on.
+    fn to_html_pretty(self) -> String
+    where
+        Self: Sized,
+    {
+        RenderContext::with_current(RenderContext::clone)
+            .pretty(true)
+            .run(|| self.to_html())
+    }
+
+    /// Renders a view to an HTML string, like [`to_html`](RenderHtml::to_html), along with each
+    /// distinct URL that its `href` and `src` attributes refer to.
+    ///
+    /// This lets a build tool that generates static pages prefetch or verify the stylesheets,
+    /// scripts, images, and links that each page uses.
+    fn to_html_with_assets(self) -> (String, Vec<AssetRef>)
+    where
+        Self: Sized,
+    {
+        collect_assets(|| self.to_html())
     }
 
     /// Renders a view to HTML with branch markers. This can be used to support libraries that diff
@@ -162,7 +227,7 @@ where
             true,
             vec![],
         );
-        buf
+        check_html_limit(strip_if_enabled(buf))
     }
 
     /// Renders a view to an in-order stream of HTML.
@@ -181,6 +246,36 @@ where
         builder.finish()
     }
 
+    /// Renders a view to an in-order stream of HTML, which is serialized lazily, in chunks of
+    /// roughly [`html_chunk_budget`](crate::ssr::RenderContext::html_chunk_budget) bytes (16 KiB
+    /// unless the current render context sets it).
+    ///
+    /// Once a chunk is full, the rest of the list being rendered is only serialized after the
+    /// chunk has been sent and the stream is polled again, and the stream yields to the executor
+    /// in between, so a huge tree doesn't hold the executor for the whole render. A chunk can
+    /// still go past the budget inside a single list item, or in a tree without lists.
+    ///
+    /// The chunks concatenate to the same HTML as [`to_html`](RenderHtml::to_html), which remains
+    /// the faster choice when the whole string is needed at once.
+    fn to_html_stream(self) -> impl Stream<Item = String> + Send
+    where
+        Self: Sized,
+    {
+        let cx = RenderContext::with_current(RenderContext::clone);
+        let budget = cx.html_chunk_budget.unwrap_or(DEFAULT_HTML_CHUNK_BUDGET);
+        cx.html_chunk_budget(budget).run(|| {
+            let mut builder = StreamBuilder::with_capacity(budget, None);
+            self.to_html_async_with_buf::<false>(
+                &mut builder,
+                &mut Position::FirstChild,
+                true,
+                false,
+                vec![],
+            );
+            builder.finish()
+        })
+    }
+
     /// Renders a view to an in-order stream of HTML with branch markers. This can be used to support libraries that diff
     /// HTML pages against one another, by marking sections of the view that branch to different
     /// types with marker comments.
@@ -239,6 +334,12 @@ where
     }
 
     /// Renders a view to HTML, writing it into the given buffer.
+    ///
+    /// `escape` is whether text in the view is HTML-escaped. An element doesn't pass it on to its
+    /// children, whose escaping depends on the element's
+    /// [`ESCAPE_CHILDREN`](crate::html::element::ElementType::ESCAPE_CHILDREN) instead. If it
+    /// did, any text nested in a `<noscript>` or `<textarea>` would be written verbatim, and text
+    /// from an untrusted source could inject markup there.
     fn to_html_with_buf(
         self,
         buf: &mut String,
@@ -259,6 +360,7 @@ where
     ) where
         Self: Sized,
     {
+        buf.reserve_html_len(&self);
         buf.with_buf(|buf| {
             self.to_html_with_buf(
                 buf,
@@ -515,6 +617,53 @@ pub enum Position {
     LastChild,
 }
 
+impl Position {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Position::Current => "Current",
+            Position::FirstChild => "FirstChild",
+            Position::NextChild => "NextChild",
+            Position::NextChildAfterText => "NextChildAfterText",
+            Position::OnlyChild => "OnlyChild",
+            Position::LastChild => "LastChild",
+        }
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Position {
+    type Err = ParsePositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Current" => Ok(Position::Current),
+            "FirstChild" => Ok(Position::FirstChild),
+            "NextChild" => Ok(Position::NextChild),
+            "NextChildAfterText" => Ok(Position::NextChildAfterText),
+            "OnlyChild" => Ok(Position::OnlyChild),
+            "LastChild" => Ok(Position::LastChild),
+            _ => Err(ParsePositionError(s.to_string())),
+        }
+    }
+}
+
+/// The error returned when a string does not name a [`Position`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePositionError(String);
+
+impl fmt::Display for ParsePositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} is not a valid position", self.0)
+    }
+}
+
+impl Error for ParsePositionError {}
+
 /// Declares that this type can be converted into some other type, which can be rendered.
 pub trait IntoRender {
     /// The renderable type into which this type can be converted.
@@ -534,3 +683,23 @@ where
         self
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Position;
+
+    #[test]
+    fn position_round_trips_through_its_name() {
+        for position in [
+            Position::Current,
+            Position::FirstChild,
+            Position::NextChild,
+            Position::NextChildAfterText,
+            Position::OnlyChild,
+            Position::LastChild,
+        ] {
+            assert_eq!(position.to_string().parse(), Ok(position));
+        }
+        assert!("Sibling".parse::<Position>().is_err());
+    }
+}
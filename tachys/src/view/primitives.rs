@@ -73,6 +73,7 @@ macro_rules! render_primitive {
 				type Owned = Self;
 
 				const MIN_LENGTH: usize = 0;
+				const DYNAMIC: bool = false;
 
                 fn dry_resolve(&mut self) {}
 
@@ -160,7 +161,6 @@ render_primitive![
     f32,
     f64,
     char,
-    bool,
     IpAddr,
     SocketAddr,
     SocketAddrV4,
@@ -180,3 +180,83 @@ render_primitive![
     NonZeroIsize,
     NonZeroUsize,
 ];
+
+// A `bool` renders nothing, so that a condition that leaks into a view (for example, from
+// `{condition && view}`) doesn't print `true` or `false`. Use `.to_string()` to show it as text.
+impl Render for bool {
+    type State = ();
+
+    fn build(self) -> Self::State {}
+
+    fn rebuild(self, _state: &mut Self::State) {}
+}
+
+no_attrs!(bool);
+
+impl RenderHtml for bool {
+    type AsyncOutput = Self;
+    type Owned = Self;
+
+    const MIN_LENGTH: usize = 0;
+    const EXISTS: bool = false;
+    const DYNAMIC: bool = false;
+
+    fn html_len(&self) -> usize {
+        0
+    }
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn to_html_with_buf(
+        self,
+        _buf: &mut String,
+        _position: &mut Position,
+        _escape: bool,
+        _mark_branches: bool,
+        _extra_attrs: Vec<AnyAttribute>,
+    ) {
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        _cursor: &Cursor,
+        _position: &PositionState,
+    ) -> Self::State {
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
+
+impl ToTemplate for bool {
+    const TEMPLATE: &'static str = "";
+
+    fn to_template(
+        _buf: &mut String,
+        _class: &mut String,
+        _style: &mut String,
+        _inner_html: &mut String,
+        _position: &mut Position,
+    ) {
+    }
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use crate::{
+        html::element::{p, ElementChild},
+        view::RenderHtml,
+    };
+
+    #[test]
+    fn bool_renders_nothing() {
+        assert_eq!(true.html_len(), 0);
+        assert_eq!(p().child(true).to_html(), "<p></p>");
+        assert_eq!(p().child((false, "text", true)).to_html(), "<p>text</p>");
+    }
+}
@@ -0,0 +1,232 @@
+use super::{
+    add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml,
+};
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::Cursor,
+    ssr::StreamBuilder,
+};
+use std::borrow::Cow;
+
+/// A transparent wrapper that reports how many bytes of HTML its view renders to.
+///
+/// With the `profile` feature enabled, each time the view is rendered to HTML, its label and
+/// size are passed to the logger set with [`set_size_probe_logger`] (or printed, by default).
+/// Without the feature, it has no effect. This can be used to find which parts of a page
+/// contribute the most to its weight.
+///
+/// When streaming, only the HTML that is rendered synchronously is counted.
+#[derive(Debug, Clone)]
+pub struct SizeProbe<V> {
+    label: Cow<'static, str>,
+    view: V,
+}
+
+impl<V> SizeProbe<V> {
+    /// Wraps the view in a probe with the given label.
+    pub fn new(label: impl Into<Cow<'static, str>>, view: V) -> Self {
+        Self {
+            label: label.into(),
+            view,
+        }
+    }
+}
+
+#[cfg_attr(not(feature = "profile"), allow(unused_variables))]
+fn report(label: &str, bytes: usize) {
+    #[cfg(feature = "profile")]
+    profile::report(label, bytes);
+}
+
+#[cfg(feature = "profile")]
+pub use profile::set_size_probe_logger;
+
+#[cfg(feature = "profile")]
+mod profile {
+    use std::sync::{Arc, RwLock};
+
+    type Logger = Arc<dyn Fn(&str, usize) + Send + Sync>;
+
+    static LOGGER: RwLock<Option<Logger>> = RwLock::new(None);
+
+    /// Sets the function that is called with the label and size, in bytes, of every
+    /// [`SizeProbe`](super::SizeProbe) that is rendered.
+    pub fn set_size_probe_logger(
+        logger: impl Fn(&str, usize) + Send + Sync + 'static,
+    ) {
+        *LOGGER.write().unwrap_or_else(|e| e.into_inner()) =
+            Some(Arc::new(logger));
+    }
+
+    pub(super) fn report(label: &str, bytes: usize) {
+        let logger = LOGGER.read().unwrap_or_else(|e| e.into_inner()).clone();
+        match logger {
+            Some(logger) => logger(label, bytes),
+            None => {
+                let message = format!("[size probe] {label}: {bytes} bytes");
+                #[cfg(all(target_family = "wasm", target_os = "unknown"))]
+                web_sys::console::log_1(&message.into());
+                #[cfg(not(all(
+                    target_family = "wasm",
+                    target_os = "unknown"
+                )))]
+                eprintln!("{message}");
+            }
+        }
+    }
+}
+
+impl<V> Render for SizeProbe<V>
+where
+    V: Render,
+{
+    type State = V::State;
+
+    fn build(self) -> Self::State {
+        self.view.build()
+    }
+
+    fn rebuild(self, state: &mut Self::State) {
+        self.view.rebuild(state);
+    }
+}
+
+impl<V> AddAnyAttr for SizeProbe<V>
+where
+    V: AddAnyAttr,
+{
+    type Output<SomeNewAttr: Attribute> = SizeProbe<V::Output<SomeNewAttr>>;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        attr: NewAttr,
+    ) -> Self::Output<NewAttr>
+    where
+        Self::Output<NewAttr>: RenderHtml,
+    {
+        SizeProbe {
+            label: self.label,
+            view: self.view.add_any_attr(attr),
+        }
+    }
+}
+
+impl<V> RenderHtml for SizeProbe<V>
+where
+    V: RenderHtml,
+{
+    type AsyncOutput = SizeProbe<V::AsyncOutput>;
+    type Owned = SizeProbe<V::Owned>;
+
+    const MIN_LENGTH: usize = V::MIN_LENGTH;
+    const EXISTS: bool = V::EXISTS;
+    const DYNAMIC: bool = V::DYNAMIC;
+
+    fn html_len(&self) -> usize {
+        self.view.html_len()
+    }
+
+    fn dry_resolve(&mut self) {
+        self.view.dry_resolve();
+    }
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        SizeProbe {
+            label: self.label,
+            view: self.view.resolve().await,
+        }
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) {
+        let start = buf.len();
+        self.view.to_html_with_buf(
+            buf,
+            position,
+            escape,
+            mark_branches,
+            extra_attrs,
+        );
+        report(&self.label, buf.len() - start);
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        escape: bool,
+        mark_branches: bool,
+        extra_attrs: Vec<AnyAttribute>,
+    ) where
+        Self: Sized,
+    {
+        let start = buf.sync_buf.len();
+        self.view.to_html_async_with_buf::<OUT_OF_ORDER>(
+            buf,
+            position,
+            escape,
+            mark_branches,
+            extra_attrs,
+        );
+        // pushing an async chunk flushes the sync buffer, so anything rendered before it is
+        // not counted here
+        report(&self.label, buf.sync_buf.len().saturating_sub(start));
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.view.hydrate::<FROM_SERVER>(cursor, position)
+    }
+
+    async fn hydrate_async(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        self.view.hydrate_async(cursor, position).await
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        SizeProbe {
+            label: self.label,
+            view: self.view.into_owned(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ssr", feature = "profile"))]
+mod tests {
+    use super::{set_size_probe_logger, SizeProbe};
+    use crate::{
+        html::element::{li, ul, ElementChild},
+        view::RenderHtml,
+    };
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn probe_logs_size_without_changing_output() {
+        let logged = Arc::new(Mutex::new(Vec::new()));
+        set_size_probe_logger({
+            let logged = Arc::clone(&logged);
+            move |label, bytes| {
+                logged.lock().unwrap().push((label.to_string(), bytes))
+            }
+        });
+
+        let list = || ul().child((li().child("a"), li().child("b")));
+        let html = SizeProbe::new("list", list()).to_html();
+        assert_eq!(html, list().to_html());
+
+        let logged = logged.lock().unwrap();
+        assert!(logged.contains(&("list".to_string(), html.len())));
+    }
+}
@@ -13,6 +13,7 @@ use crate::{
     },
     hydration::Cursor,
     renderer::{CastFrom, Rndr},
+    ssr::DefaultAttr,
 };
 use std::marker::PhantomData;
 
@@ -84,7 +85,10 @@ where
         _style: &mut String,
         _inner_html: &mut String,
     ) {
-        AttributeValue::to_html(V, K::KEY, buf)
+        AttributeValue::to_html(V, K::KEY, buf);
+        if let Some(attr) = const { DefaultAttr::from_key(K::KEY) } {
+            attr.set_explicitly();
+        }
     }
 
     fn hydrate<const FROM_SERVER: bool>(
@@ -170,6 +174,7 @@ impl<const V: &'static str> RenderHtml for Static<V> {
     type Owned = Self;
 
     const MIN_LENGTH: usize = V.len();
+    const DYNAMIC: bool = false;
 
     fn dry_resolve(&mut self) {}
 
@@ -196,8 +201,7 @@ impl<const V: &'static str> RenderHtml for Static<V> {
         if V.is_empty() && escape {
             buf.push(' ');
         } else if escape {
-            let escaped = html_escape::encode_text(V);
-            buf.push_str(&escaped);
+            super::strings::escape_text(V, buf);
         } else {
             buf.push_str(V);
         }
@@ -0,0 +1,383 @@
+use super::{
+    add_attr::AddAnyAttr, Mountable, Position, PositionState, Render,
+    RenderHtml,
+};
+use crate::{
+    html::attribute::{any_attribute::AnyAttribute, Attribute},
+    hydration::{failed_to_cast_marker_node, Cursor},
+    renderer::{
+        types::{Element, Node, Placeholder},
+        CastFrom, Rndr,
+    },
+    ssr::{StreamBuilder, StreamChunk},
+};
+use futures::{AsyncRead, AsyncReadExt};
+use std::{collections::VecDeque, future::Future, pin::Pin};
+
+const CHUNK_SIZE: usize = 8 * 1024;
+const END_MARKER: &str = "sf";
+
+/// A view that streams trusted HTML from an [`AsyncRead`] as it arrives.
+///
+/// This is useful for proxying large pre-rendered content into a response without
+/// buffering all of it first. The bytes are pushed into the stream as-is, without escaping,
+/// so they should only come from a trusted source. Invalid UTF-8 is replaced with
+/// `U+FFFD`. If reading fails, the error is logged and the content ends early, with whatever
+/// was read before the error.
+///
+/// The content can only be read while rendering to a stream: when rendered synchronously
+/// (for example, with [`RenderHtml::to_html`]), it renders nothing. On the client, it hydrates
+/// by taking ownership of the nodes that were streamed in.
+pub struct StreamFrom<R> {
+    reader: R,
+}
+
+impl<R> StreamFrom<R> {
+    /// Creates a view that streams its HTML from the reader.
+    pub fn new(reader: R) -> Self {
+        Self { reader }
+    }
+}
+
+/// Retained view state for [`StreamFrom`].
+pub struct StreamFromState {
+    nodes: Vec<Node>,
+    marker: Placeholder,
+}
+
+impl Mountable for StreamFromState {
+    fn unmount(&mut self) {
+        for node in &self.nodes {
+            Rndr::remove(node);
+        }
+        self.marker.unmount();
+    }
+
+    fn mount(&mut self, parent: &Element, marker: Option<&Node>) {
+        for node in &self.nodes {
+            Rndr::insert_node(parent, node, marker);
+        }
+        self.marker.mount(parent, marker);
+    }
+
+    fn insert_before_this(&self, child: &mut dyn Mountable) -> bool {
+        match self.nodes.first() {
+            Some(first) => {
+                let parent =
+                    Rndr::get_parent(first).and_then(Element::cast_from);
+                if let Some(parent) = parent {
+                    child.mount(&parent, Some(first));
+                    return true;
+                }
+                false
+            }
+            None => self.marker.insert_before_this(child),
+        }
+    }
+
+    fn elements(&self) -> Vec<Element> {
+        self.nodes
+            .iter()
+            .filter_map(|node| Element::cast_from(node.clone()))
+            .collect()
+    }
+}
+
+impl<R> Render for StreamFrom<R> {
+    type State = StreamFromState;
+
+    fn build(self) -> Self::State {
+        StreamFromState {
+            nodes: Vec::new(),
+            marker: Rndr::create_placeholder(),
+        }
+    }
+
+    fn rebuild(self, _state: &mut Self::State) {}
+}
+
+impl<R> AddAnyAttr for StreamFrom<R>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    type Output<SomeNewAttr: Attribute> = Self;
+
+    fn add_any_attr<NewAttr: Attribute>(
+        self,
+        _attr: NewAttr,
+    ) -> Self::Output<NewAttr> {
+        self
+    }
+}
+
+impl<R> RenderHtml for StreamFrom<R>
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    type AsyncOutput = Self;
+    type Owned = Self;
+
+    const MIN_LENGTH: usize = 0;
+
+    fn dry_resolve(&mut self) {}
+
+    async fn resolve(self) -> Self::AsyncOutput {
+        self
+    }
+
+    fn to_html_with_buf(
+        self,
+        buf: &mut String,
+        position: &mut Position,
+        _escape: bool,
+        _mark_branches: bool,
+        _extra_attrs: Vec<AnyAttribute>,
+    ) {
+        buf.push_str("<!--");
+        buf.push_str(END_MARKER);
+        buf.push_str("-->");
+        *position = Position::NextChild;
+    }
+
+    fn to_html_async_with_buf<const OUT_OF_ORDER: bool>(
+        self,
+        buf: &mut StreamBuilder,
+        position: &mut Position,
+        _escape: bool,
+        _mark_branches: bool,
+        _extra_attrs: Vec<AnyAttribute>,
+    ) where
+        Self: Sized,
+    {
+        buf.push_async(read_chunks(ChunkReader {
+            reader: self.reader,
+            bytes: vec![0; CHUNK_SIZE],
+            pending: Vec::new(),
+        }));
+        *position = Position::NextChild;
+    }
+
+    fn hydrate<const FROM_SERVER: bool>(
+        self,
+        cursor: &Cursor,
+        position: &PositionState,
+    ) -> Self::State {
+        if position.get() == Position::FirstChild {
+            cursor.child();
+        } else {
+            cursor.sibling();
+        }
+
+        // every node until the end marker was streamed in from the reader
+        let mut nodes = Vec::new();
+        let marker = loop {
+            let node = cursor.current();
+            match Placeholder::cast_from(node.clone()) {
+                Some(marker)
+                    if node.text_content().as_deref() == Some(END_MARKER) =>
+                {
+                    break marker
+                }
+                // the parent ended without the end marker
+                _ if Rndr::next_sibling(&node).is_none() => {
+                    break failed_to_cast_marker_node(node)
+                }
+                _ => {
+                    nodes.push(node);
+                    cursor.sibling();
+                }
+            }
+        };
+        position.set(Position::NextChild);
+
+        StreamFromState { nodes, marker }
+    }
+
+    fn into_owned(self) -> Self::Owned {
+        self
+    }
+}
+
+type ChunkFuture = Pin<Box<dyn Future<Output = VecDeque<StreamChunk>> + Send>>;
+
+/// The reader, with the buffer that is reused for every read and the bytes of a character
+/// that was split across reads.
+struct ChunkReader<R> {
+    reader: R,
+    bytes: Vec<u8>,
+    pending: Vec<u8>,
+}
+
+/// Reads a single chunk, and defers reading the rest to a new async chunk, so that each
+/// piece of the content can be sent as soon as it arrives.
+fn read_chunks<R>(mut state: ChunkReader<R>) -> ChunkFuture
+where
+    R: AsyncRead + Unpin + Send + 'static,
+{
+    Box::pin(async move {
+        let read = match state.reader.read(&mut state.bytes).await {
+            Ok(read) => read,
+            Err(e) => {
+                report_read_error(&e);
+                0
+            }
+        };
+        let done = read == 0;
+        state.pending.extend_from_slice(&state.bytes[..read]);
+
+        let mut html = take_utf8(&mut state.pending, done);
+        let mut chunks = VecDeque::new();
+        if done {
+            html.push_str("<!--");
+            html.push_str(END_MARKER);
+            html.push_str("-->");
+            chunks.push_back(StreamChunk::Sync(html));
+        } else {
+            if !html.is_empty() {
+                chunks.push_back(StreamChunk::Sync(html));
+            }
+            chunks.push_back(StreamChunk::Async {
+                chunks: read_chunks(state),
+            });
+        }
+        chunks
+    })
+}
+
+/// Logs an error that cut the streamed content short. The end marker is still sent, so that
+/// the rest of the page can be hydrated.
+fn report_read_error(error: &std::io::Error) {
+    #[cfg(feature = "tracing")]
+    tracing::error!("StreamFrom failed to read its content: {error}");
+    #[cfg(not(feature = "tracing"))]
+    eprintln!("StreamFrom failed to read its content: {error}");
+}
+
+/// Takes as much valid UTF-8 as possible from the start of `pending`, leaving behind an
+/// incomplete character at the end so it can be completed by the next read.
+fn take_utf8(pending: &mut Vec<u8>, done: bool) -> String {
+    let mut out = String::with_capacity(pending.len());
+    loop {
+        match std::str::from_utf8(pending) {
+            Ok(valid) => {
+                out.push_str(valid);
+                pending.clear();
+                return out;
+            }
+            Err(e) => {
+                let valid_up_to = e.valid_up_to();
+                out.push_str(&String::from_utf8_lossy(&pending[..valid_up_to]));
+                match e.error_len() {
+                    Some(invalid) => {
+                        out.push(char::REPLACEMENT_CHARACTER);
+                        pending.drain(..valid_up_to + invalid);
+                    }
+                    None if done => {
+                        out.push(char::REPLACEMENT_CHARACTER);
+                        pending.clear();
+                        return out;
+                    }
+                    None => {
+                        pending.drain(..valid_up_to);
+                        return out;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{take_utf8, StreamFrom};
+    use crate::view::RenderHtml;
+    use futures::{executor::block_on, AsyncRead, StreamExt};
+    use std::{
+        io,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    /// Returns at most one byte per read, so every multi-byte character is split.
+    struct OneByteAtATime(&'static [u8]);
+
+    impl AsyncRead for OneByteAtATime {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            match self.0.split_first() {
+                Some((first, rest)) if !buf.is_empty() => {
+                    buf[0] = *first;
+                    self.0 = rest;
+                    Poll::Ready(Ok(1))
+                }
+                _ => Poll::Ready(Ok(0)),
+            }
+        }
+    }
+
+    #[test]
+    fn streams_all_content_from_reader() {
+        let content = "<p>Grüße, 世界! 🦀</p>".repeat(1000);
+        let reader = futures::io::Cursor::new(content.clone().into_bytes());
+        let html = block_on(
+            StreamFrom::new(reader)
+                .to_html_stream_in_order()
+                .collect::<String>(),
+        );
+        assert_eq!(html, format!("{content}<!--sf-->"));
+    }
+
+    #[test]
+    fn joins_characters_split_across_reads() {
+        let content = "añb€c🦀";
+        let reader = OneByteAtATime(content.as_bytes());
+        let chunks = block_on(
+            StreamFrom::new(reader)
+                .to_html_stream_in_order()
+                .collect::<Vec<_>>(),
+        );
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks.concat(), format!("{content}<!--sf-->"));
+    }
+
+    /// Returns its content on the first read, and fails on the next one.
+    struct FailsAfter(Option<&'static [u8]>);
+
+    impl AsyncRead for FailsAfter {
+        fn poll_read(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+            buf: &mut [u8],
+        ) -> Poll<io::Result<usize>> {
+            match self.0.take() {
+                Some(content) => {
+                    buf[..content.len()].copy_from_slice(content);
+                    Poll::Ready(Ok(content.len()))
+                }
+                None => Poll::Ready(Err(io::ErrorKind::BrokenPipe.into())),
+            }
+        }
+    }
+
+    #[test]
+    fn stops_at_read_error() {
+        let html = block_on(
+            StreamFrom::new(FailsAfter(Some(b"<p>partial")))
+                .to_html_stream_in_order()
+                .collect::<String>(),
+        );
+        assert_eq!(html, "<p>partial<!--sf-->");
+    }
+
+    #[test]
+    fn replaces_invalid_utf8() {
+        let mut pending = vec![b'a', 0xff, b'b', 0xe2, 0x82];
+        assert_eq!(take_utf8(&mut pending, false), "a\u{fffd}b");
+        assert_eq!(pending, [0xe2, 0x82]);
+        assert_eq!(take_utf8(&mut pending, true), "\u{fffd}");
+        assert!(pending.is_empty());
+    }
+}
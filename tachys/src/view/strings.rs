@@ -42,6 +42,7 @@ impl RenderHtml for &str {
     type Owned = String;
 
     const MIN_LENGTH: usize = 0;
+    const DYNAMIC: bool = false;
 
     fn dry_resolve(&mut self) {}
 
@@ -68,8 +69,7 @@ impl RenderHtml for &str {
         if self.is_empty() && escape {
             buf.push(' ');
         } else if escape {
-            let escaped = html_escape::encode_text(self);
-            buf.push_str(&escaped);
+            escape_text(self, buf);
         } else {
             buf.push_str(self);
         }
@@ -176,6 +176,7 @@ impl Render for String {
 
 impl RenderHtml for String {
     const MIN_LENGTH: usize = 0;
+    const DYNAMIC: bool = false;
     type AsyncOutput = Self;
     type Owned = Self;
 
@@ -293,6 +294,7 @@ where
     type AsyncOutput = Self;
 
     const MIN_LENGTH: usize = 0;
+    const DYNAMIC: bool = false;
 
     async fn resolve(self) -> Self::AsyncOutput {
     self
@@ -384,6 +386,7 @@ impl RenderHtml for Arc<str> {
     type Owned = Self;
 
     const MIN_LENGTH: usize = 0;
+    const DYNAMIC: bool = false;
 
     fn dry_resolve(&mut self) {}
 
@@ -495,6 +498,7 @@ impl RenderHtml for Cow<'_, str> {
     type Owned = String;
 
     const MIN_LENGTH: usize = 0;
+    const DYNAMIC: bool = false;
 
     fn dry_resolve(&mut self) {}
 
@@ -577,3 +581,57 @@ impl Mountable for CowStrState<'_> {
         vec![]
     }
 }
+
+/// Escapes text for use as the contents of an HTML element, pushing it into the buffer.
+///
+/// The output is identical to [`html_escape::encode_text`], but runs of text that don't need
+/// escaping are found with `memchr` and copied in bulk, which is much faster for long text.
+pub(crate) fn escape_text(text: &str, buf: &mut String) {
+    let bytes = text.as_bytes();
+    let mut start = 0;
+    for idx in memchr::memchr3_iter(b'&', b'<', b'>', bytes) {
+        // all three are ASCII, so `idx` is always on a char boundary
+        buf.push_str(&text[start..idx]);
+        buf.push_str(match bytes[idx] {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            _ => "&gt;",
+        });
+        start = idx + 1;
+    }
+    buf.push_str(&text[start..]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape_text;
+
+    fn assert_matches_naive(text: &str) {
+        let mut buf = String::new();
+        escape_text(text, &mut buf);
+        assert_eq!(buf, html_escape::encode_text(text));
+    }
+
+    #[test]
+    fn escape_text_large_clean_string() {
+        assert_matches_naive(&"Lorem ipsum dolor sit amet. ".repeat(10_000));
+    }
+
+    #[test]
+    fn escape_text_sparse_special_chars() {
+        let text = format!(
+            "{}<b>{}&amp;{}>",
+            "a".repeat(5_000),
+            "ünïcödé ".repeat(5_000),
+            "z".repeat(5_000)
+        );
+        assert_matches_naive(&text);
+    }
+
+    #[test]
+    fn escape_text_edge_cases() {
+        for text in ["", "&", "<>", "&&&", "a<", ">a", "\"quotes\" 'too'"] {
+            assert_matches_naive(text);
+        }
+    }
+}
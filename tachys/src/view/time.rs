@@ -0,0 +1,293 @@
+use super::{
+    add_attr::AddAnyAttr, Position, PositionState, Render, RenderHtml,
+};
+use crate::{
+    html::{
+        attribute::{any_attribute::AnyAttribute, Attr, Attribute, Datetime},
+        element::{HtmlElement, Time},
+    },
+    hydration::Cursor,
+};
+use std::{fmt::Write, time::Duration};
+
+type TimeElement = HtmlElement<Time, (Attr<Datetime, String>,), (String,)>;
+
+/// A value that is rendered as a `<time>` element.
+trait AsTime {
+    /// The machine-readable value of the `datetime` attribute.
+    fn datetime(&self) -> String;
+
+    /// The human-readable text inside the element.
+    fn text(&self) -> String;
+
+    fn to_time_element(&self) -> TimeElement {
+        HtmlElement {
+            #[cfg(any(debug_assertions, leptos_debuginfo))]
+            defined_at: std::panic::Location::caller(),
+            tag: Time,
+            attributes: (Attr(Datetime, self.datetime()),),
+            children: (self.text(),),
+        }
+    }
+}
+
+macro_rules! render_as_time {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl Render for $ty {
+                type State = <TimeElement as Render>::State;
+
+                fn build(self) -> Self::State {
+                    self.to_time_element().build()
+                }
+
+                fn rebuild(self, state: &mut Self::State) {
+                    self.to_time_element().rebuild(state);
+                }
+            }
+
+            impl AddAnyAttr for $ty {
+                type Output<SomeNewAttr: Attribute> =
+                    <TimeElement as AddAnyAttr>::Output<SomeNewAttr>;
+
+                fn add_any_attr<NewAttr: Attribute>(
+                    self,
+                    attr: NewAttr,
+                ) -> Self::Output<NewAttr>
+                where
+                    Self::Output<NewAttr>: RenderHtml,
+                {
+                    self.to_time_element().add_any_attr(attr)
+                }
+            }
+
+            impl RenderHtml for $ty {
+                type AsyncOutput = Self;
+                type Owned = Self;
+
+                const MIN_LENGTH: usize =
+                    <TimeElement as RenderHtml>::MIN_LENGTH;
+                const DYNAMIC: bool = false;
+
+                fn html_len(&self) -> usize {
+                    self.to_time_element().html_len()
+                }
+
+                fn dry_resolve(&mut self) {}
+
+                async fn resolve(self) -> Self::AsyncOutput {
+                    self
+                }
+
+                fn to_html_with_buf(
+                    self,
+                    buf: &mut String,
+                    position: &mut Position,
+                    escape: bool,
+                    mark_branches: bool,
+                    extra_attrs: Vec<AnyAttribute>,
+                ) {
+                    self.to_time_element().to_html_with_buf(
+                        buf,
+                        position,
+                        escape,
+                        mark_branches,
+                        extra_attrs,
+                    );
+                }
+
+                fn hydrate<const FROM_SERVER: bool>(
+                    self,
+                    cursor: &Cursor,
+                    position: &PositionState,
+                ) -> Self::State {
+                    self.to_time_element()
+                        .hydrate::<FROM_SERVER>(cursor, position)
+                }
+
+                fn into_owned(self) -> Self::Owned {
+                    self
+                }
+            }
+        )*
+    };
+}
+
+/// Writes whole seconds, followed by up to three fractional digits if there are any.
+fn push_seconds(buf: &mut String, secs: u64, millis: u32) {
+    _ = write!(buf, "{secs}");
+    if millis > 0 {
+        let fraction = format!("{millis:03}");
+        buf.push('.');
+        buf.push_str(fraction.trim_end_matches('0'));
+    }
+}
+
+impl AsTime for Duration {
+    // a duration string, like `PT1H2M3.5S`; HTML allows at most three fractional digits
+    fn datetime(&self) -> String {
+        let secs = self.as_secs();
+        let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+        let millis = self.subsec_millis();
+        let mut buf = String::from("PT");
+        if hours > 0 {
+            _ = write!(buf, "{hours}H");
+        }
+        if minutes > 0 {
+            _ = write!(buf, "{minutes}M");
+        }
+        if secs > 0 || millis > 0 || buf.len() == 2 {
+            push_seconds(&mut buf, secs, millis);
+            buf.push('S');
+        }
+        buf
+    }
+
+    fn text(&self) -> String {
+        let secs = self.as_secs();
+        let (hours, minutes, secs) = (secs / 3600, secs / 60 % 60, secs % 60);
+        let millis = self.subsec_millis();
+        let mut buf = String::new();
+        if hours > 0 {
+            _ = write!(buf, "{hours}h ");
+        }
+        if minutes > 0 {
+            _ = write!(buf, "{minutes}m ");
+        }
+        if secs > 0 || millis > 0 || buf.is_empty() {
+            push_seconds(&mut buf, secs, millis);
+            buf.push('s');
+        }
+        buf.trim_end().to_string()
+    }
+}
+
+render_as_time!(Duration);
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::{
+        AddAnyAttr, AnyAttribute, AsTime, Attribute, Cursor, Position,
+        PositionState, Render, RenderHtml, TimeElement,
+    };
+    use chrono::{
+        DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike,
+        Utc,
+    };
+
+    // HTML allows at most three fractional digits for seconds
+    fn seconds_format(time: &impl Timelike) -> &'static str {
+        if time.nanosecond() == 0 {
+            "%H:%M:%S"
+        } else {
+            "%H:%M:%S%.3f"
+        }
+    }
+
+    impl AsTime for NaiveDate {
+        fn datetime(&self) -> String {
+            self.format("%Y-%m-%d").to_string()
+        }
+
+        fn text(&self) -> String {
+            self.format("%B %-d, %Y").to_string()
+        }
+    }
+
+    impl AsTime for NaiveTime {
+        fn datetime(&self) -> String {
+            self.format(seconds_format(self)).to_string()
+        }
+
+        fn text(&self) -> String {
+            self.format("%H:%M").to_string()
+        }
+    }
+
+    impl AsTime for NaiveDateTime {
+        fn datetime(&self) -> String {
+            format!("{}T{}", self.date().datetime(), self.time().datetime())
+        }
+
+        fn text(&self) -> String {
+            self.format("%B %-d, %Y %H:%M").to_string()
+        }
+    }
+
+    impl AsTime for DateTime<Utc> {
+        fn datetime(&self) -> String {
+            format!("{}Z", self.naive_utc().datetime())
+        }
+
+        fn text(&self) -> String {
+            self.format("%B %-d, %Y %H:%M UTC").to_string()
+        }
+    }
+
+    impl AsTime for DateTime<FixedOffset> {
+        fn datetime(&self) -> String {
+            format!("{}{}", self.naive_local().datetime(), self.format("%:z"))
+        }
+
+        fn text(&self) -> String {
+            self.format("%B %-d, %Y %H:%M %:z").to_string()
+        }
+    }
+
+    render_as_time!(
+        NaiveDate,
+        NaiveTime,
+        NaiveDateTime,
+        DateTime<Utc>,
+        DateTime<FixedOffset>,
+    );
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use crate::{
+        html::element::{p, ElementChild},
+        view::RenderHtml,
+    };
+    use std::time::Duration;
+
+    #[test]
+    fn renders_duration() {
+        let html = p().child(Duration::from_secs(3723)).to_html();
+        assert_eq!(html, r#"<p><time datetime="PT1H2M3S">1h 2m 3s</time></p>"#);
+    }
+
+    #[test]
+    fn renders_fractional_and_zero_durations() {
+        assert_eq!(
+            Duration::from_millis(90_250).to_html(),
+            r#"<time datetime="PT1M30.25S">1m 30.25s</time>"#
+        );
+        assert_eq!(
+            Duration::from_secs(7200).to_html(),
+            r#"<time datetime="PT2H">2h</time>"#
+        );
+        assert_eq!(
+            Duration::ZERO.to_html(),
+            r#"<time datetime="PT0S">0s</time>"#
+        );
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn renders_chrono_dates() {
+        use chrono::{NaiveDate, TimeZone, Utc};
+
+        let date = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+        assert_eq!(
+            date.to_html(),
+            r#"<time datetime="2024-03-05">March 5, 2024</time>"#
+        );
+
+        let instant = Utc.with_ymd_and_hms(2024, 3, 5, 14, 30, 0).unwrap();
+        assert_eq!(
+            instant.to_html(),
+            r#"<time datetime="2024-03-05T14:30:00Z">March 5, 2024 14:30 UTC</time>"#
+        );
+    }
+}
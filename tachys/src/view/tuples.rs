@@ -27,6 +27,7 @@ impl RenderHtml for () {
 
     const MIN_LENGTH: usize = 3;
     const EXISTS: bool = false;
+    const DYNAMIC: bool = false;
 
     fn to_html_with_buf(
         self,
@@ -135,6 +136,7 @@ where
 
     const MIN_LENGTH: usize = A::MIN_LENGTH;
     const EXISTS: bool = A::EXISTS;
+    const DYNAMIC: bool = A::DYNAMIC;
 
     fn html_len(&self) -> usize {
         self.0.html_len()
@@ -277,6 +279,7 @@ macro_rules! impl_view_for_tuples {
             type Owned = ($first::Owned, $($ty::Owned,)*);
             const EXISTS: bool = $first::EXISTS || $($ty::EXISTS || )* false;
             const MIN_LENGTH: usize = $first::MIN_LENGTH $(+ $ty::MIN_LENGTH)*;
+            const DYNAMIC: bool = $first::DYNAMIC $(|| $ty::DYNAMIC)*;
 
             #[inline(always)]
             fn html_len(&self) -> usize {
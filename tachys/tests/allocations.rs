@@ -0,0 +1,179 @@
+//! These tests count allocations with a global allocator, so they live in their own test
+//! binary rather than replacing the allocator for every unit test.
+#![cfg(feature = "ssr")]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+use tachys::{
+    html::{
+        attribute::Attribute,
+        class::class,
+        element::{div, ElementType, HtmlElement},
+    },
+    prelude::*,
+};
+
+// counts allocations and reallocations on the current thread, so that
+// tests running in parallel don't affect each other
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCS: Cell<usize> = const { Cell::new(0) };
+    static REALLOCS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        _ = ALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        _ = REALLOCS.try_with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+struct ClassHeavyDiv;
+
+impl ElementType for ClassHeavyDiv {
+    type Output = web_sys::HtmlDivElement;
+
+    const TAG: &'static str = "div";
+    const SELF_CLOSING: bool = false;
+    const ESCAPE_CHILDREN: bool = true;
+    const NAMESPACE: Option<&'static str> = None;
+    const CLASS_CAPACITY_HINT: usize = 256;
+
+    fn tag(&self) -> &str {
+        Self::TAG
+    }
+}
+
+fn utility_classes() -> impl Attribute {
+    (
+        class(("flex", true)),
+        class(("items-center", true)),
+        class(("justify-between", true)),
+        class(("rounded-lg", true)),
+        class(("border", true)),
+        class(("border-gray-200", true)),
+        class(("bg-white", true)),
+        class(("px-4", true)),
+        class(("py-2", true)),
+        class(("shadow-sm", true)),
+        class(("hover:bg-gray-50", true)),
+        class(("focus:outline-none", true)),
+    )
+}
+
+fn render_counting_reallocs(view: impl RenderHtml) -> (String, usize) {
+    let before = REALLOCS.with(Cell::get);
+    let html = view.to_html();
+    (html, REALLOCS.with(Cell::get) - before)
+}
+
+#[test]
+fn class_capacity_hint_avoids_reallocations() {
+    let (plain_html, plain_reallocs) =
+        render_counting_reallocs(div().add_any_attr(utility_classes()));
+    let (hinted_html, hinted_reallocs) = render_counting_reallocs(
+        HtmlElement::new(ClassHeavyDiv).add_any_attr(utility_classes()),
+    );
+
+    assert_eq!(hinted_html, plain_html);
+    assert!(
+        hinted_reallocs < plain_reallocs,
+        "{hinted_reallocs} reallocations with a hint, {plain_reallocs} \
+         without"
+    );
+}
+
+#[cfg(feature = "buffer_pool")]
+#[test]
+fn buffer_pool_cuts_allocations_per_request() {
+    use futures::{executor::block_on, StreamExt};
+    use tachys::html::element::p;
+
+    let request = || {
+        let before = ALLOCS.with(Cell::get);
+        let page =
+            div().child((0..200).map(|n| p().child(n)).collect::<Vec<_>>());
+        let html = block_on(page.to_html_stream_in_order().collect::<String>());
+        (html, ALLOCS.with(Cell::get) - before)
+    };
+
+    // the first request fills the pool; later ones draw from it
+    let (cold_html, cold_allocs) = request();
+    let (warm_html, warm_allocs) = request();
+    assert_eq!(warm_html, cold_html);
+    assert!(
+        warm_allocs < cold_allocs,
+        "{warm_allocs} allocations with a warm pool, {cold_allocs} with an \
+         empty one"
+    );
+}
+
+#[test]
+fn reserve_html_len_cuts_reallocations_for_large_tables() {
+    use futures::{executor::block_on, StreamExt};
+    use tachys::{
+        html::element::{table, td, tr},
+        ssr::{RenderContext, StreamBuilder},
+        view::Position,
+    };
+
+    // 1,000 rows of 10 cells each
+    let table = || {
+        table().child(
+            (0..1000)
+                .map(|_| {
+                    tr().child(
+                        (0..10).map(|col| td().child(col)).collect::<Vec<_>>(),
+                    )
+                })
+                .collect::<Vec<_>>(),
+        )
+    };
+    let stream = |cx: RenderContext| {
+        cx.run(|| {
+            let view = table();
+            let mut buf = StreamBuilder::new(None);
+            let before = REALLOCS.with(Cell::get);
+            view.to_html_async_with_buf::<false>(
+                &mut buf,
+                &mut Position::FirstChild,
+                true,
+                false,
+                vec![],
+            );
+            let reallocs = REALLOCS.with(Cell::get) - before;
+            (block_on(buf.collect::<String>()), reallocs)
+        })
+    };
+
+    let (plain_html, plain_reallocs) = stream(RenderContext::new());
+    let (reserved_html, reserved_reallocs) =
+        stream(RenderContext::new().reserve_html_len_below(64));
+
+    assert_eq!(reserved_html, plain_html);
+    assert!(
+        reserved_reallocs < plain_reallocs,
+        "{reserved_reallocs} reallocations with reserve_html_len_below, \
+         {plain_reallocs} without"
+    );
+}
@@ -0,0 +1,27 @@
+#![cfg(target_family = "wasm")]
+
+use tachys::{
+    dom::document,
+    html::element::{button, span},
+    prelude::*,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn labelled_button_builds_aria_attributes() {
+    let parent = document().create_element("div").unwrap();
+    let mut state = button()
+        .aria_label("Close")
+        .aria_labelledby("dialog-title")
+        .child(span().aria_hidden(true).child("×"))
+        .build();
+    state.mount(&parent, None);
+
+    assert_eq!(
+        parent.inner_html(),
+        "<button aria-label=\"Close\" aria-labelledby=\"dialog-title\"><span \
+         aria-hidden=\"true\">×</span></button>"
+    );
+}
@@ -0,0 +1,47 @@
+#![cfg(target_family = "wasm")]
+
+use tachys::{
+    dom::document,
+    html::{
+        attribute::{disabled, Attribute},
+        element::{button, p},
+    },
+    prelude::*,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn rebuilding_to_none_removes_attribute() {
+    let parent = document().create_element("div").unwrap();
+    let mut state = p().id(Some("a")).build();
+    state.mount(&parent, None);
+    assert_eq!(parent.inner_html(), r#"<p id="a"></p>"#);
+
+    p().id(None::<&str>).rebuild(&mut state);
+    assert_eq!(parent.inner_html(), "<p></p>");
+
+    p().id(Some("b")).rebuild(&mut state);
+    assert_eq!(parent.inner_html(), r#"<p id="b"></p>"#);
+}
+
+#[wasm_bindgen_test]
+fn boolean_attribute_toggles_by_presence() {
+    let parent = document().create_element("div").unwrap();
+    let mut state = button().disabled(true).build();
+    state.mount(&parent, None);
+    assert_eq!(parent.inner_html(), "<button disabled=\"\"></button>");
+
+    button().disabled(false).rebuild(&mut state);
+    assert_eq!(parent.inner_html(), "<button></button>");
+}
+
+#[wasm_bindgen_test]
+fn false_boolean_attribute_is_not_set_when_hydrating_template() {
+    let el = document().create_element("button").unwrap();
+    disabled(false).hydrate::<false>(&el);
+    assert!(!el.has_attribute("disabled"));
+    disabled(true).hydrate::<false>(&el);
+    assert!(el.has_attribute("disabled"));
+}
@@ -0,0 +1,27 @@
+#![cfg(target_family = "wasm")]
+
+use tachys::{dom::document, html::element::div, prelude::*};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn data_attributes_are_set_and_updated() {
+    let parent = document().create_element("section").unwrap();
+    let mut state = div()
+        .data_attr("user-id", "1")
+        .data_attr("state", "idle")
+        .build();
+    state.mount(&parent, None);
+
+    let el = parent.first_element_child().unwrap();
+    assert_eq!(el.get_attribute("data-user-id").as_deref(), Some("1"));
+    assert_eq!(el.get_attribute("data-state").as_deref(), Some("idle"));
+
+    div()
+        .data_attr("user-id", "2")
+        .data_attr("state", "loading")
+        .rebuild(&mut state);
+    assert_eq!(el.get_attribute("data-user-id").as_deref(), Some("2"));
+    assert_eq!(el.get_attribute("data-state").as_deref(), Some("loading"));
+}
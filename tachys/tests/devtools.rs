@@ -0,0 +1,77 @@
+#![cfg(all(feature = "devtools", target_family = "wasm"))]
+
+use std::sync::{Arc, Mutex};
+use tachys::{
+    devtools::{
+        add_lifecycle_observer, remove_lifecycle_observer, LifecycleKind,
+    },
+    dom::document,
+    html::element::{p, span},
+    prelude::*,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn observer_receives_mount_and_unmount() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let id = add_lifecycle_observer({
+        let events = Arc::clone(&events);
+        move |event| {
+            events
+                .lock()
+                .unwrap()
+                .push((event.kind, event.tag.to_string()))
+        }
+    });
+
+    let parent = document().create_element("div").unwrap();
+    let mut state = p().child(span().child("hi")).build();
+    state.mount(&parent, None);
+    state.unmount();
+    remove_lifecycle_observer(id);
+
+    let events = events.lock().unwrap();
+    let expected = [
+        (LifecycleKind::Build, "p"),
+        (LifecycleKind::Build, "span"),
+        (LifecycleKind::Mount, "span"),
+        (LifecycleKind::Mount, "p"),
+        (LifecycleKind::Unmount, "p"),
+    ];
+    assert_eq!(events.len(), expected.len());
+    for ((kind, tag), (expected_kind, expected_tag)) in
+        events.iter().zip(expected)
+    {
+        assert_eq!((*kind, tag.as_str()), (expected_kind, expected_tag));
+    }
+}
+
+#[wasm_bindgen_test]
+fn replaced_children_are_unmounted() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let parent = document().create_element("div").unwrap();
+    let mut state = p().child(span().child("old")).build();
+    state.mount(&parent, None);
+
+    let id = add_lifecycle_observer({
+        let events = Arc::clone(&events);
+        move |event| {
+            events
+                .lock()
+                .unwrap()
+                .push((event.kind, event.tag.to_string()))
+        }
+    });
+    state.replace_children((span().child("new"),));
+    remove_lifecycle_observer(id);
+
+    let events = events.lock().unwrap();
+    assert_eq!(
+        events.first().map(|(kind, _)| *kind),
+        Some(LifecycleKind::Unmount)
+    );
+    assert_eq!(events.first().map(|(_, tag)| tag.as_str()), Some("span"));
+    assert_eq!(parent.inner_html(), "<p><span>new</span></p>");
+}
@@ -0,0 +1,67 @@
+#![cfg(target_family = "wasm")]
+
+use tachys::{
+    dom::document,
+    html::element::{li, ul},
+    prelude::*,
+    view::template::ViewTemplate,
+};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Counts calls to `document.createElement` made after this is called.
+fn count_create_element() -> impl Fn() -> u32 {
+    js_sys::Function::new_no_args(
+        "const create = Document.prototype.createElement;
+         globalThis.__createElementCalls = 0;
+         Document.prototype.createElement = function (...args) {
+             globalThis.__createElementCalls += 1;
+             return create.apply(this, args);
+         };",
+    )
+    .call0(&JsValue::NULL)
+    .unwrap();
+    || {
+        js_sys::Reflect::get(
+            &js_sys::global(),
+            &JsValue::from_str("__createElementCalls"),
+        )
+        .unwrap()
+        .as_f64()
+        .unwrap() as u32
+    }
+}
+
+#[wasm_bindgen_test]
+fn static_rows_are_cloned_from_a_template() {
+    let calls = count_create_element();
+    let rows = (0..5_000)
+        .map(|_| ViewTemplate::new(li().class("row").child("static")))
+        .collect::<Vec<_>>();
+    let parent = document().create_element("div").unwrap();
+    let mut state = ul().child(rows).build();
+    state.mount(&parent, None);
+
+    assert_eq!(parent.query_selector_all("li.row").unwrap().length(), 5_000);
+    // the <ul>, and the <template> that every row is cloned from
+    assert!(calls() <= 2, "{} calls to createElement", calls());
+}
+
+#[wasm_bindgen_test]
+fn rows_cloned_from_a_template_are_independent() {
+    let parent = document().create_element("div").unwrap();
+    let row = || ViewTemplate::new(li().class("a").child("a"));
+    let mut state = (row(), row()).build();
+    state.mount(&parent, None);
+
+    let first = parent.first_element_child().unwrap();
+    let second = first.next_element_sibling().unwrap();
+    first.set_attribute("data-x", "1").unwrap();
+    first.set_text_content(Some("changed"));
+
+    assert_eq!(second.class_name(), "a");
+    assert!(!second.has_attribute("data-x"));
+    assert_eq!(second.text_content().as_deref(), Some("a"));
+}
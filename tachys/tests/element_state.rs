@@ -0,0 +1,45 @@
+#![cfg(target_family = "wasm")]
+
+use tachys::{
+    dom::document,
+    html::element::{em, li, p, ul},
+    prelude::*,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn built_element_state_can_be_inspected() {
+    let state = p()
+        .id("x")
+        .lang("en")
+        .child(("text", em().child("a"), em().child("b")))
+        .build();
+
+    assert_eq!(state.local_name(), "p");
+    assert_eq!(state.get_attribute("id").as_deref(), Some("x"));
+    assert_eq!(state.get_attribute("lang").as_deref(), Some("en"));
+    assert_eq!(state.get_attribute("title"), None);
+    assert_eq!(state.child_element_count(), 2);
+}
+
+#[wasm_bindgen_test]
+fn replace_children_swaps_the_whole_subtree() {
+    let items = |names: &[&'static str]| {
+        names
+            .iter()
+            .map(|name| li().child(*name))
+            .collect::<Vec<_>>()
+    };
+    let parent = document().create_element("div").unwrap();
+    let mut state = ul().child(items(&["a", "b", "c"])).build();
+    state.mount(&parent, None);
+    assert_eq!(
+        parent.inner_html(),
+        "<ul><li>a</li><li>b</li><li>c</li><!----></ul>"
+    );
+
+    state.replace_children((items(&["d", "e"]),));
+    assert_eq!(parent.inner_html(), "<ul><li>d</li><li>e</li><!----></ul>");
+}
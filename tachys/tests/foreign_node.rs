@@ -0,0 +1,39 @@
+#![cfg(target_family = "wasm")]
+
+use tachys::{
+    dom::document, html::element::div, prelude::*,
+    view::foreign_node::ForeignNode,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn mounts_existing_node_as_child() {
+    let chart = document().create_element("canvas").unwrap();
+    chart.set_id("chart");
+
+    let parent = document().create_element("section").unwrap();
+    let mut state = div().child(ForeignNode::new(chart.clone())).build();
+    state.mount(&parent, None);
+
+    let container = parent.first_element_child().unwrap();
+    assert_eq!(container.inner_html(), r#"<canvas id="chart"></canvas>"#);
+    assert!(chart.parent_node().is_some());
+}
+
+#[wasm_bindgen_test]
+fn unmount_detaches_without_destroying() {
+    let chart = document().create_element("canvas").unwrap();
+    let parent = document().create_element("section").unwrap();
+    let mut state = ForeignNode::new(chart.clone()).build();
+
+    state.mount(&parent, None);
+    assert_eq!(parent.child_element_count(), 1);
+    state.unmount();
+    assert!(chart.parent_node().is_none());
+
+    // the same node can be mounted again
+    state.mount(&parent, None);
+    assert!(parent.first_child().unwrap().is_same_node(Some(&chart)));
+}
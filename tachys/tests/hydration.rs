@@ -0,0 +1,121 @@
+#![cfg(all(feature = "testing", target_family = "wasm"))]
+
+use tachys::{
+    dom::document,
+    html::element::{em, li, main, p, ul},
+    hydration::Cursor,
+    prelude::*,
+    ssr::RenderContext,
+    testing::assert_hydrates,
+    view::{
+        comment_marker::CommentMarker, stream_from::StreamFrom, Position,
+        PositionState,
+    },
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn nested_elements_with_text_siblings_hydrate() {
+    let view = main().child((
+        p().id("greeting").lang("en").child((
+            "Hello, ",
+            em().child("beautiful"),
+            " world!",
+        )),
+        ul().child((li().child(1), li().child("two"), li().child(3.5))),
+        "trailing ",
+        "text",
+    ));
+    assert_hydrates(view);
+}
+
+#[wasm_bindgen_test]
+fn omitted_closing_tags_parse_to_same_tree() {
+    let view = main().child((
+        ul().child((li().child("one"), li().child("two"), li().child("three"))),
+        p().child("after"),
+    ));
+    RenderContext::new()
+        .omit_optional_tags(true)
+        .run(|| assert_hydrates(view));
+}
+
+#[wasm_bindgen_test]
+fn cursor_records_the_path_it_walked() {
+    let view = || main().child((p(), p()));
+    let container = document().create_element("div").unwrap();
+    container.set_inner_html(&view().to_html());
+
+    let cursor = Cursor::new(container);
+    cursor.start_trace();
+    view().hydrate::<true>(&cursor, &PositionState::new(Position::FirstChild));
+
+    let trace = cursor
+        .trace()
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ");
+    assert_eq!(
+        trace,
+        "FirstChild FirstChild Current NextChild Current Current"
+    );
+    let parsed = trace
+        .split(' ')
+        .map(|position| position.parse().unwrap())
+        .collect::<Vec<Position>>();
+    assert_eq!(parsed, cursor.trace());
+}
+
+#[wasm_bindgen_test]
+fn sibling_n_skips_over_siblings() {
+    let container = document().create_element("div").unwrap();
+    for i in 0..100 {
+        let child = document().create_element("span").unwrap();
+        child.set_text_content(Some(&i.to_string()));
+        container.append_child(&child).unwrap();
+    }
+    let cursor = Cursor::new(container);
+    cursor.child();
+    let text = || cursor.current().text_content().unwrap();
+
+    cursor.sibling_n(0);
+    assert_eq!(text(), "0");
+    cursor.sibling_n(50);
+    assert_eq!(text(), "50");
+    cursor.sibling_n(1000);
+    assert_eq!(text(), "99");
+}
+
+#[wasm_bindgen_test]
+fn comment_marker_hydrates_onto_its_comment() {
+    let view = || (p(), CommentMarker, p());
+    let container = document().create_element("div").unwrap();
+    container.set_inner_html(&view().to_html());
+    assert_eq!(container.inner_html(), "<p></p><!--hk--><p></p>");
+
+    let cursor = Cursor::new(container.clone());
+    let (_, marker, _) = view()
+        .hydrate::<true>(&cursor, &PositionState::new(Position::FirstChild));
+    assert_eq!(marker.text_content().as_deref(), Some("hk"));
+    assert!(container
+        .child_nodes()
+        .item(1)
+        .is_some_and(|node| node.is_same_node(Some(&marker))));
+}
+
+#[wasm_bindgen_test]
+fn children_from_iter_builds_and_hydrates() {
+    let view = || ul().children_from_iter((1..=5).map(|n| li().child(n)));
+    let parent = document().create_element("div").unwrap();
+    let mut state = view().build();
+    state.mount(&parent, None);
+    assert_eq!(
+        parent.inner_html(),
+        "<ul><li>1</li><li>2</li><li>3</li><li>4</li><li>5</li><!----></ul>"
+    );
+
+    assert_hydrates(view());
+}
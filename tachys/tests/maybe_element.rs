@@ -0,0 +1,52 @@
+#![cfg(all(feature = "testing", target_family = "wasm"))]
+
+use tachys::{
+    dom::document,
+    html::element::{li, ul, Li, MaybeElement},
+    prelude::*,
+    testing::assert_hydrates,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+fn item(show: bool) -> MaybeElement<Li, (), (&'static str,)> {
+    show.then(|| li().child("b")).into()
+}
+
+#[wasm_bindgen_test]
+fn toggling_mounts_and_unmounts_in_place() {
+    let parent = document().create_element("div").unwrap();
+    let mut state = (item(true), li().child("c")).build();
+    state.mount(&parent, None);
+    assert_eq!(parent.inner_html(), "<li>b</li><li>c</li>");
+
+    (item(false), li().child("c")).rebuild(&mut state);
+    assert_eq!(parent.inner_html(), "<!----><li>c</li>");
+
+    (item(true), li().child("c")).rebuild(&mut state);
+    assert_eq!(parent.inner_html(), "<li>b</li><li>c</li>");
+}
+
+#[wasm_bindgen_test]
+fn present_element_hydrates() {
+    assert_hydrates(ul().child((li().child("a"), item(true))));
+}
+
+#[wasm_bindgen_test]
+fn missing_element_can_be_added_after_hydration() {
+    let view =
+        |show| ul().child((li().child("a"), item(show), li().child("c")));
+    let html = view(false).to_html();
+    assert_eq!(html, "<ul><li>a</li><li>c</li></ul>");
+
+    let container = document().create_element("div").unwrap();
+    container.set_inner_html(&html);
+    let mut state = view(false).hydrate_from::<true>(&container);
+
+    view(true).rebuild(&mut state);
+    assert_eq!(
+        container.inner_html(),
+        "<ul><li>a</li><li>b</li><li>c</li></ul>"
+    );
+}
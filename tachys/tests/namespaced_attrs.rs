@@ -0,0 +1,25 @@
+#![cfg(target_family = "wasm")]
+
+use tachys::{
+    dom::document,
+    html::attribute::namespaced::{xlink_href, XLINK_NAMESPACE},
+    prelude::*,
+    svg::r#use,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn builds_attribute_in_namespace() {
+    let parent = document().create_element("div").unwrap();
+    let mut state = r#use().add_any_attr(xlink_href("#icon")).build();
+    state.mount(&parent, None);
+
+    let el = parent.first_element_child().unwrap();
+    assert_eq!(
+        el.get_attribute_ns(Some(XLINK_NAMESPACE), "href")
+            .as_deref(),
+        Some("#icon")
+    );
+}
@@ -0,0 +1,27 @@
+#![cfg(all(feature = "reactive_graph", target_family = "wasm"))]
+
+use reactive_graph::traits::GetUntracked;
+use tachys::{
+    dom::document,
+    html::element::{input, Input},
+    prelude::*,
+    reactive_graph::node_ref::NodeRef,
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+#[wasm_bindgen_test]
+fn node_ref_is_cleared_when_element_is_removed() {
+    let parent = document().create_element("div").unwrap();
+    let node_ref = NodeRef::<Input>::new();
+
+    let mut state = input().node_ref(node_ref).build();
+    state.mount(&parent, None);
+    let el = node_ref.get_untracked().expect("node_ref was not loaded");
+    assert_eq!(parent.first_child().as_ref(), Some(el.as_ref()));
+
+    state.unmount();
+    drop(state);
+    assert!(node_ref.get_untracked().is_none());
+}
@@ -0,0 +1,43 @@
+#![cfg(target_family = "wasm")]
+
+use tachys::{dom::document, html::element::input, prelude::*};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_futures::JsFuture;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+// `value` is written in a microtask, so give it one to run.
+async fn tick() {
+    _ = JsFuture::from(js_sys::Promise::resolve(&JsValue::NULL)).await;
+}
+
+fn property(el: &web_sys::Element, key: &str) -> JsValue {
+    js_sys::Reflect::get(el, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+async fn prop_sets_the_property_not_the_attribute() {
+    let mut state = input().prop("value", "typed").build();
+    tick().await;
+    assert_eq!(property(&state, "value"), "typed");
+    assert_eq!(state.get_attribute("value"), None);
+
+    input().prop("value", "retyped").rebuild(&mut state);
+    tick().await;
+    assert_eq!(property(&state, "value"), "retyped");
+    assert_eq!(state.get_attribute("value"), None);
+}
+
+#[wasm_bindgen_test]
+async fn hydrated_prop_takes_over_from_the_server_attribute() {
+    let container = document().create_element("div").unwrap();
+    container.set_inner_html(r#"<input value="server">"#);
+
+    let state = input()
+        .prop("value", "client")
+        .hydrate_from::<true>(&container);
+    tick().await;
+    assert_eq!(property(&state, "value"), "client");
+    assert_eq!(state.get_attribute("value").as_deref(), Some("server"));
+}
@@ -0,0 +1,40 @@
+#![cfg(target_family = "wasm")]
+
+use tachys::{
+    dom::document,
+    mathml::{math, mi},
+    prelude::*,
+    svg::{circle, svg},
+};
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+const SVG_NAMESPACE: &str = "http://www.w3.org/2000/svg";
+const MATHML_NAMESPACE: &str = "http://www.w3.org/1998/Math/MathML";
+
+#[wasm_bindgen_test]
+fn svg_children_are_built_in_svg_namespace() {
+    let parent = document().create_element("div").unwrap();
+    let mut state = svg().child(circle()).build();
+    state.mount(&parent, None);
+
+    let svg = parent.first_element_child().unwrap();
+    let circle = svg.first_element_child().unwrap();
+    assert_eq!(svg.namespace_uri().as_deref(), Some(SVG_NAMESPACE));
+    assert_eq!(circle.namespace_uri().as_deref(), Some(SVG_NAMESPACE));
+    assert_eq!(circle.local_name(), "circle");
+}
+
+#[wasm_bindgen_test]
+fn mathml_children_are_built_in_mathml_namespace() {
+    let parent = document().create_element("div").unwrap();
+    let mut state = math().child(mi().child("x")).build();
+    state.mount(&parent, None);
+
+    let mi = parent.first_element_child().unwrap().first_element_child();
+    assert_eq!(
+        mi.unwrap().namespace_uri().as_deref(),
+        Some(MATHML_NAMESPACE)
+    );
+}